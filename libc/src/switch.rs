@@ -20,14 +20,108 @@ pub type uintptr_t = usize;
 pub type ssize_t = isize;
 
 pub type off_t = i64;
+// aarch64 Horizon's AAPCS64 ABI defines `char` as unsigned, unlike the x86
+// targets in this crate where it is signed. Struct fields and function
+// signatures that come from Horizon headers must use `c_char`, not `c_schar`,
+// so this distinction round-trips correctly through FFI.
 pub type c_char = u8;
 pub type c_long = i64;
 pub type c_ulong = u64;
 pub type wchar_t = u32;
 
+pub type time_t = i64;
+pub type suseconds_t = i64;
+pub type clockid_t = c_int;
+
+pub type pthread_key_t = c_uint;
+pub type pid_t = c_int;
+pub type nfds_t = u32;
+
 pub const INT_MIN: c_int = -2147483648;
 pub const INT_MAX: c_int = 2147483647;
 
+pub const INTMAX_MIN: intmax_t = -9223372036854775808;
+pub const INTMAX_MAX: intmax_t = 9223372036854775807;
+pub const UINTMAX_MAX: uintmax_t = 18446744073709551615;
+
+// aarch64 Horizon is LP64: `size_t`/`ssize_t`/`off_t` are all 64-bit.
+pub const SIZE_MAX: size_t = 18446744073709551615;
+pub const SSIZE_MAX: ssize_t = 9223372036854775807;
+
+pub const OFF_MIN: off_t = -9223372036854775808;
+pub const OFF_MAX: off_t = 9223372036854775807;
+
+// newlib (the Horizon libc) represents `sigset_t` as a single `unsigned long`
+// bitmask rather than the larger opaque struct glibc uses.
+pub type sigset_t = c_ulong;
+pub type sighandler_t = size_t;
+
+pub const SIG_DFL: sighandler_t = 0 as sighandler_t;
+pub const SIG_IGN: sighandler_t = 1 as sighandler_t;
+pub const SIG_ERR: sighandler_t = !0 as sighandler_t;
+
+// Signal handling on Horizon is only partially emulated by newlib/libnx, but
+// the numbering matches the standard POSIX assignments other newlib targets
+// use, so code that only compares against these constants still works.
+pub const SIGHUP: ::c_int = 1;
+pub const SIGINT: ::c_int = 2;
+pub const SIGQUIT: ::c_int = 3;
+pub const SIGILL: ::c_int = 4;
+pub const SIGTRAP: ::c_int = 5;
+pub const SIGABRT: ::c_int = 6;
+pub const SIGEMT: ::c_int = 7;
+pub const SIGFPE: ::c_int = 8;
+pub const SIGKILL: ::c_int = 9;
+pub const SIGBUS: ::c_int = 10;
+pub const SIGSEGV: ::c_int = 11;
+pub const SIGSYS: ::c_int = 12;
+pub const SIGPIPE: ::c_int = 13;
+pub const SIGALRM: ::c_int = 14;
+pub const SIGTERM: ::c_int = 15;
+
+pub const CLOCK_REALTIME: clockid_t = 0;
+pub const CLOCK_MONOTONIC: clockid_t = 1;
+
+s! {
+    pub struct timeval {
+        pub tv_sec: time_t,
+        pub tv_usec: suseconds_t,
+    }
+
+    pub struct timespec {
+        pub tv_sec: time_t,
+        pub tv_nsec: c_long,
+    }
+
+    pub struct iovec {
+        pub iov_base: *mut c_void,
+        pub iov_len: size_t,
+    }
+
+    pub struct pollfd {
+        pub fd: c_int,
+        pub events: c_short,
+        pub revents: c_short,
+    }
+}
+
+// Horizon's sockets layer (libnx's `bsd:u`/`bsd:s` services) only emulates
+// readability/writability/error notification; there's no analogue of
+// `POLLPRI`, `POLLRDHUP`, or the other Linux-specific bits some other
+// targets define, so only these three are provided.
+pub const POLLIN: c_short = 0x1;
+pub const POLLOUT: c_short = 0x4;
+pub const POLLERR: c_short = 0x8;
+
+extern "C" {
+    pub fn clock_gettime(clk_id: clockid_t, tp: *mut timespec) -> c_int;
+    pub fn gettimeofday(tp: *mut timeval, tz: *mut c_void) -> c_int;
+
+    pub fn nanosleep(rqtp: *const timespec, rmtp: *mut timespec) -> c_int;
+    pub fn sleep(secs: c_uint) -> c_uint;
+    pub fn usleep(secs: c_uint) -> c_int;
+}
+
 cfg_if! {
     if #[cfg(libc_core_cvoid)] {
         pub use ::ffi::c_void;
@@ -47,3 +141,157 @@ cfg_if! {
         }
     }
 }
+
+#[cfg_attr(feature = "extra_traits", derive(Debug))]
+pub enum FILE {}
+impl ::Copy for FILE {}
+impl ::Clone for FILE {
+    fn clone(&self) -> FILE {
+        *self
+    }
+}
+
+extern "C" {
+    pub fn strerror(n: c_int) -> *mut c_char;
+    pub fn strlen(cs: *const c_char) -> size_t;
+    pub fn memcpy(dest: *mut c_void, src: *const c_void, n: size_t) -> *mut c_void;
+    pub fn memmove(dest: *mut c_void, src: *const c_void, n: size_t) -> *mut c_void;
+    pub fn memset(dest: *mut c_void, c: c_int, n: size_t) -> *mut c_void;
+    pub fn memcmp(cx: *const c_void, ct: *const c_void, n: size_t) -> c_int;
+}
+
+extern "C" {
+    pub fn fopen(filename: *const c_char, mode: *const c_char) -> *mut FILE;
+    pub fn fclose(file: *mut FILE) -> c_int;
+    pub fn fread(ptr: *mut c_void, size: size_t, nobj: size_t, stream: *mut FILE) -> size_t;
+    pub fn fwrite(ptr: *const c_void, size: size_t, nobj: size_t, stream: *mut FILE) -> size_t;
+    pub fn fseek(stream: *mut FILE, offset: c_long, whence: c_int) -> c_int;
+    pub fn ftell(stream: *mut FILE) -> c_long;
+
+    pub fn malloc(size: size_t) -> *mut c_void;
+    pub fn calloc(nobj: size_t, size: size_t) -> *mut c_void;
+    pub fn realloc(p: *mut c_void, size: size_t) -> *mut c_void;
+    pub fn free(p: *mut c_void);
+    pub fn posix_memalign(memptr: *mut *mut c_void, align: size_t, size: size_t) -> c_int;
+}
+
+pub const PROT_READ: c_int = 0x1;
+pub const PROT_WRITE: c_int = 0x2;
+
+pub const MAP_PRIVATE: c_int = 0x02;
+pub const MAP_ANONYMOUS: c_int = 0x20;
+
+extern "C" {
+    // Horizon's mmap is backed by libnx's virtual memory manager rather than
+    // a real kernel page cache, so only anonymous private mappings are
+    // supported: `MAP_SHARED` and file-backed mappings (a non-negative `fd`)
+    // are rejected with `ENOTSUP`, and `mprotect` cannot change a mapping's
+    // executable bit after creation.
+    pub fn mmap(
+        addr: *mut c_void,
+        len: size_t,
+        prot: c_int,
+        flags: c_int,
+        fd: c_int,
+        offset: off_t,
+    ) -> *mut c_void;
+    pub fn munmap(addr: *mut c_void, len: size_t) -> c_int;
+    pub fn mprotect(addr: *mut c_void, len: size_t, prot: c_int) -> c_int;
+}
+
+extern "C" {
+    pub fn pthread_key_create(
+        key: *mut pthread_key_t,
+        dtor: Option<unsafe extern "C" fn(*mut c_void)>,
+    ) -> c_int;
+    pub fn pthread_key_delete(key: pthread_key_t) -> c_int;
+    pub fn pthread_getspecific(key: pthread_key_t) -> *mut c_void;
+    pub fn pthread_setspecific(key: pthread_key_t, value: *const c_void) -> c_int;
+}
+
+extern "C" {
+    // Horizon newlib implements these against an in-process environment
+    // block rather than a real OS environment: there is no concept of a
+    // per-process environment inherited from a parent, so the block starts
+    // empty unless the homebrew loader populates it, and changes are never
+    // visible to anything outside the calling process.
+    pub fn getenv(name: *const c_char) -> *mut c_char;
+    pub fn setenv(name: *const c_char, value: *const c_char, overwrite: c_int) -> c_int;
+    pub fn unsetenv(name: *const c_char) -> c_int;
+}
+
+extern "C" {
+    // Horizon has no concept of a process tree: there is exactly one
+    // userland process per title, so newlib's `getpid` always returns the
+    // same constant PID rather than anything assigned by a kernel scheduler.
+    pub fn getpid() -> pid_t;
+
+    // libnx doesn't expose a `getrandom`/`arc4random_buf`-style syscall
+    // wrapper, so newlib falls back to a single-threaded ChaCha-based CSPRNG
+    // seeded from the hardware RNG at startup; the `arc4random_buf` name is
+    // kept for source compatibility with other BSD-derived libcs.
+    pub fn arc4random_buf(buf: *mut c_void, nbytes: size_t);
+}
+
+extern "C" {
+    pub fn readv(fd: c_int, iov: *const iovec, iovcnt: c_int) -> ssize_t;
+    pub fn writev(fd: c_int, iov: *const iovec, iovcnt: c_int) -> ssize_t;
+}
+
+extern "C" {
+    pub fn poll(fds: *mut pollfd, nfds: nfds_t, timeout: c_int) -> c_int;
+}
+
+pub const RTLD_LAZY: c_int = 0x1;
+pub const RTLD_NOW: c_int = 0x2;
+pub const RTLD_GLOBAL: c_int = 0x100;
+pub const RTLD_LOCAL: c_int = 0;
+
+extern "C" {
+    // Horizon has no dynamic loader: homebrew titles are linked as a single
+    // static NRO/NSO image, so there is nothing for `dlopen` to load at
+    // runtime. libnx's newlib port still exports these symbols (so code
+    // written against a generic POSIX `dlfcn.h` links successfully instead
+    // of failing at link time), but they always fail: `dlopen` and `dlsym`
+    // return `NULL`, `dlclose` returns `-1`, and `dlerror` returns a
+    // non-null message describing the lack of dynamic loading support.
+    // Callers can feature-detect the limitation by checking for a `NULL`
+    // return the same way they would on a platform where the requested
+    // library or symbol is simply missing.
+    pub fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+    pub fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    pub fn dlclose(handle: *mut c_void) -> c_int;
+    pub fn dlerror() -> *mut c_char;
+}
+
+cfg_if! {
+    if #[cfg(feature = "std")] {
+        extern crate std;
+
+        // Horizon's `c_char` is `u8`, so `CString`/`CStr` (which assume a signed `c_char` on
+        // most other targets) can't be used to build the null-terminated buffers the switch
+        // externs above expect; these helpers fill that gap.
+        pub mod ffi_str {
+            use c_char;
+            use super::std::string::String;
+            use super::std::vec::Vec;
+
+            /// Converts `s` into a null-terminated buffer of `c_char`s, suitable for passing as
+            /// a `*const c_char` FFI argument.
+            pub fn to_c_string(s: &str) -> Vec<c_char> {
+                let mut buf: Vec<c_char> = s.bytes().collect();
+                buf.push(0);
+                buf
+            }
+
+            /// Reads a null-terminated `c_char` buffer back into an owned `String`, stopping at
+            /// the first nul byte (or at the end of `buf`, if it has none).
+            ///
+            /// Invalid UTF-8 is replaced per [`String::from_utf8_lossy`].
+            pub fn from_c_string(buf: &[c_char]) -> String {
+                let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+                String::from_utf8_lossy(&buf[..end]).into_owned()
+            }
+        }
+    }
+}
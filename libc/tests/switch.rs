@@ -0,0 +1,272 @@
+#![cfg(target_os = "switch")]
+
+use std::ffi::CStr;
+
+#[test]
+fn size_max_matches_usize_max() {
+    assert_eq!(libc::SIZE_MAX, usize::MAX);
+}
+
+#[test]
+fn c_char_buffer_converts_to_cstr() {
+    let mut buf: [libc::c_char; 16] = [0; 16];
+    for (i, b) in b"hello".iter().enumerate() {
+        buf[i] = *b as libc::c_char;
+    }
+    let bytes: [u8; 16] = buf.map(|c| c as u8);
+    let s = CStr::from_bytes_until_nul(&bytes).unwrap();
+    assert_eq!(s.to_str().unwrap(), "hello");
+}
+
+#[test]
+fn sigkill_is_nine() {
+    assert_eq!(libc::SIGKILL, 9);
+}
+
+#[test]
+fn sigset_t_matches_newlib_size() {
+    assert_eq!(std::mem::size_of::<libc::sigset_t>(), std::mem::size_of::<libc::c_ulong>());
+}
+
+// Compile-only: checks the stdio externs link and have the expected signatures.
+#[allow(dead_code)]
+fn stdio_symbols_have_expected_signatures() {
+    unsafe {
+        let file: *mut libc::FILE = libc::fopen(std::ptr::null(), std::ptr::null());
+        let mut buf = [0u8; 8];
+        libc::fread(buf.as_mut_ptr() as *mut libc::c_void, 1, buf.len() as libc::size_t, file);
+        libc::fwrite(buf.as_ptr() as *const libc::c_void, 1, buf.len() as libc::size_t, file);
+        libc::fseek(file, 0, 0);
+        libc::ftell(file);
+        libc::fclose(file);
+    }
+}
+
+#[test]
+fn malloc_calloc_realloc_free_round_trip() {
+    unsafe {
+        let p = libc::malloc(16);
+        assert!(!p.is_null());
+        let p = libc::realloc(p, 32);
+        assert!(!p.is_null());
+        libc::free(p);
+
+        let z = libc::calloc(4, 4);
+        assert!(!z.is_null());
+        for i in 0..16 {
+            assert_eq!(*(z as *const u8).add(i), 0);
+        }
+        libc::free(z);
+    }
+}
+
+// Compile-only: checks posix_memalign's signature.
+#[allow(dead_code)]
+unsafe fn posix_memalign_has_expected_signature(memptr: *mut *mut libc::c_void) -> libc::c_int {
+    libc::posix_memalign(memptr, 8, 16)
+}
+
+// Compile-only: checks the getenv/setenv/unsetenv externs link and have the
+// expected signatures.
+#[allow(dead_code)]
+unsafe fn env_symbols_have_expected_signatures(name: *const libc::c_char, value: *const libc::c_char) {
+    let _: *mut libc::c_char = libc::getenv(name);
+    libc::setenv(name, value, 1);
+    libc::unsetenv(name);
+}
+
+// Compile-only: checks the mmap/munmap/mprotect externs link and have the
+// expected signatures.
+#[allow(dead_code)]
+unsafe fn mmap_symbols_have_expected_signatures() {
+    let addr = libc::mmap(
+        std::ptr::null_mut(),
+        4096,
+        libc::PROT_READ | libc::PROT_WRITE,
+        libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+        -1,
+        0,
+    );
+    libc::mprotect(addr, 4096, libc::PROT_READ);
+    libc::munmap(addr, 4096);
+}
+
+// Compile-only: checks the pthread_key_* externs link and have the expected
+// signatures.
+#[allow(dead_code)]
+unsafe fn pthread_key_symbols_have_expected_signatures(key: *mut libc::pthread_key_t) -> libc::c_int {
+    libc::pthread_key_create(key, None);
+    libc::pthread_getspecific(*key);
+    libc::pthread_setspecific(*key, std::ptr::null());
+    libc::pthread_key_delete(*key)
+}
+
+#[test]
+fn pthread_key_stores_and_retrieves_a_tls_value() {
+    unsafe {
+        let mut key: libc::pthread_key_t = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, None), 0);
+
+        let value = 42u32;
+        assert_eq!(
+            libc::pthread_setspecific(key, &value as *const u32 as *const libc::c_void),
+            0
+        );
+        let retrieved = libc::pthread_getspecific(key) as *const u32;
+        assert_eq!(*retrieved, value);
+
+        assert_eq!(libc::pthread_key_delete(key), 0);
+    }
+}
+
+#[test]
+fn nanosleep_sleeps_at_least_one_millisecond() {
+    unsafe {
+        let mut start: libc::timespec = std::mem::zeroed();
+        libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut start);
+
+        let req = libc::timespec { tv_sec: 0, tv_nsec: 1_000_000 };
+        assert_eq!(libc::nanosleep(&req, std::ptr::null_mut()), 0);
+
+        let mut end: libc::timespec = std::mem::zeroed();
+        libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut end);
+
+        let elapsed_nanos = (end.tv_sec - start.tv_sec) * 1_000_000_000 + (end.tv_nsec - start.tv_nsec);
+        assert!(elapsed_nanos >= 1_000_000);
+    }
+}
+
+// Compile-only: checks the sleep/usleep externs link and have the expected
+// signatures.
+#[allow(dead_code)]
+unsafe fn sleep_symbols_have_expected_signatures() {
+    libc::sleep(0);
+    libc::usleep(0);
+}
+
+#[test]
+fn strlen_reads_a_null_terminated_buffer() {
+    unsafe {
+        let buf = b"hello\0";
+        assert_eq!(libc::strlen(buf.as_ptr() as *const libc::c_char), 5);
+    }
+}
+
+#[test]
+fn memset_fills_a_stack_buffer() {
+    unsafe {
+        let mut buf = [0u8; 8];
+        libc::memset(buf.as_mut_ptr() as *mut libc::c_void, 0xAB, buf.len() as libc::size_t);
+        assert_eq!(buf, [0xAB; 8]);
+    }
+}
+
+// Compile-only: checks the strerror/memcpy/memmove/memcmp externs link and
+// have the expected signatures.
+#[allow(dead_code)]
+unsafe fn mem_symbols_have_expected_signatures() {
+    let _: *mut libc::c_char = libc::strerror(0);
+    let mut dest = [0u8; 4];
+    let src = [1u8; 4];
+    libc::memcpy(dest.as_mut_ptr() as *mut libc::c_void, src.as_ptr() as *const libc::c_void, 4);
+    libc::memmove(dest.as_mut_ptr() as *mut libc::c_void, src.as_ptr() as *const libc::c_void, 4);
+    libc::memcmp(dest.as_ptr() as *const libc::c_void, src.as_ptr() as *const libc::c_void, 4);
+}
+
+#[test]
+fn to_c_string_appends_a_null_terminator() {
+    let buf = libc::ffi_str::to_c_string("hello");
+    assert_eq!(buf, b"hello\0".to_vec());
+}
+
+#[test]
+fn c_string_helpers_round_trip_ascii() {
+    let buf = libc::ffi_str::to_c_string("hello");
+    assert_eq!(libc::ffi_str::from_c_string(&buf), "hello");
+}
+
+#[test]
+fn c_string_helpers_round_trip_utf8() {
+    let s = "héllo wörld \u{1F980}";
+    let buf = libc::ffi_str::to_c_string(s);
+    assert_eq!(*buf.last().unwrap(), 0);
+    assert_eq!(libc::ffi_str::from_c_string(&buf), s);
+}
+
+#[test]
+fn from_c_string_stops_at_first_nul() {
+    let buf = [b'h', b'i', 0, b'X', b'X'];
+    assert_eq!(libc::ffi_str::from_c_string(&buf), "hi");
+}
+
+// Compile-only: checks the getpid/arc4random_buf externs link and have the
+// expected signatures.
+#[allow(dead_code)]
+unsafe fn pid_and_random_symbols_have_expected_signatures(buf: *mut libc::c_void, nbytes: libc::size_t) -> libc::pid_t {
+    libc::arc4random_buf(buf, nbytes);
+    libc::getpid()
+}
+
+#[test]
+fn arc4random_buf_fills_a_buffer_with_non_all_zero_bytes() {
+    unsafe {
+        let mut buf = [0u8; 32];
+        libc::arc4random_buf(buf.as_mut_ptr() as *mut libc::c_void, buf.len() as libc::size_t);
+        assert!(buf.iter().any(|&b| b != 0));
+    }
+}
+
+#[test]
+fn iovec_has_expected_layout() {
+    assert_eq!(std::mem::size_of::<libc::iovec>(), std::mem::size_of::<*mut libc::c_void>() + std::mem::size_of::<libc::size_t>());
+    assert_eq!(std::mem::align_of::<libc::iovec>(), std::mem::align_of::<*mut libc::c_void>());
+
+    let mut byte = 0u8;
+    let iov = libc::iovec { iov_base: &mut byte as *mut u8 as *mut libc::c_void, iov_len: 1 };
+    assert_eq!(iov.iov_len, 1);
+}
+
+// Compile-only: checks the readv/writev externs link and have the expected
+// signatures.
+#[allow(dead_code)]
+unsafe fn iovec_symbols_have_expected_signatures(fd: libc::c_int, iov: *const libc::iovec, iovcnt: libc::c_int) -> libc::ssize_t {
+    libc::writev(fd, iov, iovcnt);
+    libc::readv(fd, iov, iovcnt)
+}
+
+#[test]
+fn pollfd_has_expected_size() {
+    assert_eq!(
+        std::mem::size_of::<libc::pollfd>(),
+        std::mem::size_of::<libc::c_int>() + 2 * std::mem::size_of::<libc::c_short>()
+    );
+
+    let pfd = libc::pollfd { fd: 0, events: libc::POLLIN, revents: 0 };
+    assert_eq!(pfd.events, libc::POLLIN);
+}
+
+// Compile-only: checks the poll extern links and has the expected signature.
+#[allow(dead_code)]
+unsafe fn poll_symbol_has_expected_signature(fds: *mut libc::pollfd, nfds: libc::nfds_t, timeout: libc::c_int) -> libc::c_int {
+    libc::poll(fds, nfds, timeout)
+}
+
+#[test]
+fn clock_gettime_monotonic_reads_a_timespec() {
+    unsafe {
+        let mut ts: libc::timespec = std::mem::zeroed();
+        let ret = libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts);
+        assert_eq!(ret, 0);
+        assert!(ts.tv_sec > 0 || ts.tv_nsec > 0);
+    }
+}
+
+// Compile-only: checks the dlopen/dlsym/dlclose/dlerror externs link and have the expected
+// signatures, and that the RTLD_* flag constants exist with `c_int` type.
+#[allow(dead_code)]
+unsafe fn dl_symbols_have_expected_signatures(filename: *const libc::c_char, symbol: *const libc::c_char) {
+    let handle: *mut libc::c_void = libc::dlopen(filename, libc::RTLD_LAZY | libc::RTLD_NOW | libc::RTLD_GLOBAL | libc::RTLD_LOCAL);
+    let _: *mut libc::c_void = libc::dlsym(handle, symbol);
+    libc::dlclose(handle);
+    let _: *mut libc::c_char = libc::dlerror();
+}
@@ -296,3 +296,160 @@ where
         }
     }
 }
+
+/// A newtype wrapper around a dynamic-dimensional array that (de)serializes as a plain
+/// `{ shape: [..], data: [..] }` structure, unlike `ArrayBase`'s own `Serialize`/`Deserialize`
+/// impls above, which additionally carry an internal format version field. This matches the
+/// shape most non-Rust MessagePack/JSON array producers emit, so it is useful for interop where
+/// `ndarray`'s own versioned format isn't available on the other end.
+///
+/// **Requires crate feature `"serde"`**
+#[derive(Debug, Clone, PartialEq)]
+pub struct NdArrayMsg<A>(pub Array<A, IxDyn>);
+
+static ND_ARRAY_MSG_FIELDS: &[&str] = &["shape", "data"];
+
+/// **Requires crate feature `"serde"`**
+impl<A> Serialize for NdArrayMsg<A>
+where
+    A: Serialize,
+{
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: Serializer,
+    {
+        let mut state = serializer.serialize_struct("NdArrayMsg", 2)?;
+        state.serialize_field("shape", self.0.shape())?;
+        state.serialize_field("data", &Sequence(self.0.iter()))?;
+        state.end()
+    }
+}
+
+enum NdArrayMsgField {
+    Shape,
+    Data,
+}
+
+impl<'de> Deserialize<'de> for NdArrayMsgField {
+    fn deserialize<D>(deserializer: D) -> Result<NdArrayMsgField, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct NdArrayMsgFieldVisitor;
+
+        impl<'de> Visitor<'de> for NdArrayMsgFieldVisitor {
+            type Value = NdArrayMsgField;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str(r#""shape" or "data""#)
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<NdArrayMsgField, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "shape" => Ok(NdArrayMsgField::Shape),
+                    "data" => Ok(NdArrayMsgField::Data),
+                    other => Err(de::Error::unknown_field(other, ND_ARRAY_MSG_FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(NdArrayMsgFieldVisitor)
+    }
+}
+
+struct NdArrayMsgVisitor<A> {
+    _marker: PhantomData<A>,
+}
+
+impl<A> NdArrayMsgVisitor<A> {
+    fn new() -> Self {
+        NdArrayMsgVisitor { _marker: PhantomData }
+    }
+}
+
+impl<'de, A> Visitor<'de> for NdArrayMsgVisitor<A>
+where
+    A: Deserialize<'de>,
+{
+    type Value = NdArrayMsg<A>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a { shape, data } ndarray representation")
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<NdArrayMsg<A>, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let shape: Vec<Ix> = match visitor.next_element()? {
+            Some(value) => value,
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+
+        let data: Vec<A> = match visitor.next_element()? {
+            Some(value) => value,
+            None => return Err(de::Error::invalid_length(1, &self)),
+        };
+
+        build_nd_array_msg(shape, data)
+    }
+
+    fn visit_map<V>(self, mut visitor: V) -> Result<NdArrayMsg<A>, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut shape: Option<Vec<Ix>> = None;
+        let mut data: Option<Vec<A>> = None;
+
+        while let Some(key) = visitor.next_key()? {
+            match key {
+                NdArrayMsgField::Shape => {
+                    shape = Some(visitor.next_value()?);
+                }
+                NdArrayMsgField::Data => {
+                    data = Some(visitor.next_value()?);
+                }
+            }
+        }
+
+        let shape = shape.ok_or_else(|| de::Error::missing_field("shape"))?;
+        let data = data.ok_or_else(|| de::Error::missing_field("data"))?;
+
+        build_nd_array_msg(shape, data)
+    }
+}
+
+fn build_nd_array_msg<A, E>(shape: Vec<Ix>, data: Vec<A>) -> Result<NdArrayMsg<A>, E>
+where
+    E: de::Error,
+{
+    let len = shape.iter().product();
+    if data.len() != len {
+        return Err(de::Error::custom(format!(
+            "shape {:?} calls for {} elements, but data has {}",
+            shape,
+            len,
+            data.len()
+        )));
+    }
+    match ArrayBase::from_shape_vec(IxDyn(&shape), data) {
+        Ok(array) => Ok(NdArrayMsg(array)),
+        Err(err) => Err(de::Error::custom(err)),
+    }
+}
+
+/// **Requires crate feature `"serde"`**
+impl<'de, A> Deserialize<'de> for NdArrayMsg<A>
+where
+    A: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<NdArrayMsg<A>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("NdArrayMsg", ND_ARRAY_MSG_FIELDS, NdArrayMsgVisitor::new())
+    }
+}
@@ -2254,6 +2254,18 @@ where
         }
     }
 
+    /// Return the array, but with `axis` read in reverse.
+    ///
+    /// This is the consuming, by-value counterpart of [`.invert_axis()`](Self::invert_axis):
+    /// it negates the stride of `axis` and offsets the pointer to what was previously its last
+    /// element, so the same underlying elements are read back to front along that axis.
+    ///
+    /// ***Panics*** if the axis is out of bounds.
+    pub fn flipped(mut self, axis: Axis) -> Self {
+        self.invert_axis(axis);
+        self
+    }
+
     /// If possible, merge in the axis `take` to `into`.
     ///
     /// Returns `true` iff the axes are now merged.
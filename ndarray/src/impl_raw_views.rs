@@ -1,12 +1,54 @@
 use num_complex::Complex;
-use std::mem;
+use std::mem::{self, MaybeUninit};
 use std::ptr::NonNull;
 
 use crate::dimension::{self, stride_offset};
+use crate::error::{self, ErrorKind, ShapeError};
 use crate::extension::nonnull::nonnull_debug_checked_from_ptr;
 use crate::imp_prelude::*;
 use crate::is_aligned;
+use crate::iterators::Baseiter;
 use crate::shape_builder::{Strides, StrideShape};
+use crate::IntoDimension;
+
+/// Checks that `dim`/`strides` don't overflow and describe a non-negative-stride layout, and
+/// that `ptr` is properly aligned for `A`, returning a descriptive message on the first problem
+/// found. Shared by `RawArrayView::debug_validate` and `RawArrayViewMut::debug_validate`, since
+/// both views carry the same shape/stride/pointer invariants.
+fn validate_raw_view_parts<A, D: Dimension>(dim: &D, strides: &D, ptr: *const A) -> Result<(), String> {
+    dimension::strides_non_negative(strides).map_err(|err| err.to_string())?;
+    dimension::max_abs_offset_check_overflow::<A, _>(dim, strides).map_err(|err| err.to_string())?;
+    if !is_aligned(ptr) {
+        return Err("the pointer is not properly aligned for `A`".to_string());
+    }
+    Ok(())
+}
+
+/// Computes the half-open byte range `[start, end)` addressable by a view with this
+/// `dim`/`strides`/`ptr`: from the lowest to one byte past the highest address reachable by
+/// offsetting along every axis. Strides are not guaranteed non-negative here — e.g. a view
+/// produced via `invert_axis` or a negative-step slice can reach addresses below `ptr` on some
+/// axes — so each axis's contribution is bounded on both ends (`0` for an axis not taken to its
+/// extreme, `(dim[ax]-1)*strides[ax]` for one that is) rather than assumed to only extend
+/// forward from `ptr`. An empty view (any axis of length zero) addresses no bytes, so its range
+/// is the empty `[start, start)`.
+fn addressable_byte_range<A, D: Dimension>(dim: &D, strides: &D, ptr: *const A) -> (usize, usize) {
+    let base = ptr as usize;
+    if dim.size() == 0 {
+        return (base, base);
+    }
+    let mut min_offset: isize = 0;
+    let mut max_offset: isize = 0;
+    for ax in 0..dim.ndim() {
+        let extent = (dim[ax] as isize - 1) * strides[ax] as isize;
+        min_offset += extent.min(0);
+        max_offset += extent.max(0);
+    }
+    let elem_size = mem::size_of::<A>() as isize;
+    let start = (base as isize + min_offset * elem_size) as usize;
+    let end = (base as isize + (max_offset + 1) * elem_size) as usize;
+    (start, end)
+}
 
 impl<A, D> RawArrayView<A, D>
 where
@@ -85,6 +127,40 @@ where
         RawArrayView::new_(ptr, dim, strides)
     }
 
+    /// Create an `RawArrayView<A, D>` from shape information and a raw
+    /// pointer to the elements, where the strides are given in units of
+    /// bytes rather than elements.
+    ///
+    /// This matches the convention used by foreign buffer protocols such as
+    /// PEP 3118, where a buffer's `strides` field is always in bytes. Each
+    /// entry of `byte_strides` must be an exact multiple of
+    /// `size_of::<A>()`; this is checked with a debug assertion, and it's the
+    /// caller's responsibility to ensure it holds when debug assertions are
+    /// disabled.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::from_shape_ptr`]; the same requirements apply here, using
+    /// the strides after conversion to units of `A`.
+    pub unsafe fn from_shape_ptr_bytes<Sh, St>(dim: Sh, byte_strides: St, ptr: *const A) -> Self
+    where
+        Sh: IntoDimension<Dim = D>,
+        St: IntoDimension<Dim = D>,
+    {
+        let dim = dim.into_dimension();
+        let elem_size = mem::size_of::<A>();
+        let mut strides = byte_strides.into_dimension();
+        for s in strides.slice_mut() {
+            debug_assert_eq!(
+                *s % elem_size,
+                0,
+                "byte stride must be a multiple of size_of::<A>()"
+            );
+            *s /= elem_size;
+        }
+        Self::from_shape_ptr(dim.strides(strides), ptr)
+    }
+
     /// Converts to a read-only view of the array.
     ///
     /// # Safety
@@ -129,6 +205,50 @@ where
         (left, right)
     }
 
+    /// Act like a larger size and/or shape array by *broadcasting*
+    /// into a larger shape, if possible.
+    ///
+    /// Return `None` if the shapes can not be broadcast together.
+    ///
+    /// Unlike [`ArrayBase::broadcast`](crate::ArrayBase::broadcast), this only ever grows an
+    /// axis of length 1 up to the requested length by setting its stride to zero; it does not
+    /// support broadcasting into a shape of a different number of axes. Zero strides never
+    /// accumulate an out-of-bounds offset, so this is safe to perform even though the view is
+    /// raw and its elements may not be initialized or valid to read.
+    pub fn broadcast_raw(self, shape: D) -> Option<Self> {
+        if shape.ndim() != self.dim.ndim() {
+            return None;
+        }
+        let mut new_strides = self.strides.clone();
+        for ax in 0..self.dim.ndim() {
+            let from_len = self.dim[ax];
+            let to_len = shape[ax];
+            if from_len == to_len {
+                // keep stride
+            } else if from_len == 1 {
+                new_strides[ax] = 0;
+            } else {
+                return None;
+            }
+        }
+        Some(unsafe { Self::new_(self.ptr.as_ptr(), shape, new_strides) })
+    }
+
+    /// Copies the elements of this view, in C (row-major) order, into `dst`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the same pointer validity requirements as
+    /// [`Self::deref_into_view`] (every element must be initialized and valid to read), and must
+    /// additionally ensure that `dst` is valid for writes of `self.len()` elements of type `A`
+    /// and properly aligned. `dst` may not overlap this view's own memory.
+    pub unsafe fn copy_to_contiguous(&self, dst: *mut A) {
+        let iter = Baseiter::new(self.ptr.as_ptr(), self.dim.clone(), self.strides.clone());
+        for (i, src) in iter.enumerate() {
+            dst.add(i).write(src.read());
+        }
+    }
+
     /// Cast the raw pointer of the raw array view to a different type
     ///
     /// **Panics** if element size is not compatible.
@@ -148,6 +268,161 @@ where
         let ptr = self.ptr.cast::<B>();
         unsafe { RawArrayView::new(ptr, self.dim, self.strides) }
     }
+
+    /// Cast the raw pointer of the raw array view to a smaller type `B`, reinterpreting each
+    /// element of `A` as `factor` contiguous elements of `B` along `axis`.
+    ///
+    /// **Panics** if `size_of::<A>() != factor * size_of::<B>()`, or if this view is not
+    /// contiguous (stride 1) along `axis`.
+    ///
+    /// Lack of panic does not imply it is a valid cast, for the same reason as [`Self::cast`].
+    pub fn cast_with_axis<B>(self, axis: Axis, factor: usize) -> RawArrayView<B, D> {
+        assert_eq!(
+            mem::size_of::<A>(),
+            factor * mem::size_of::<B>(),
+            "size mismatch in raw view cast_with_axis"
+        );
+        assert_eq!(
+            self.strides()[axis.index()], 1,
+            "cast_with_axis requires the view to be contiguous along `axis`"
+        );
+
+        let ax = axis.index();
+        let mut dim = self.dim.clone();
+        let mut strides = self.strides.clone();
+        for i in 0..dim.ndim() {
+            if i == ax {
+                dim[i] *= factor;
+                strides[i] = 1;
+            } else {
+                strides[i] = (self.strides()[i] * factor as isize) as Ix;
+            }
+        }
+        let ptr = self.ptr.cast::<B>();
+        unsafe { RawArrayView::new_(ptr.as_ptr(), dim, strides) }
+    }
+
+    /// Reshapes this view into `shape`, recomputing C-order (row-major) strides.
+    ///
+    /// This is a raw-pointer analog of [`ArrayBase::to_shape`](crate::ArrayBase::to_shape) that
+    /// never falls back to cloning elements: since there's no access to the elements through a
+    /// raw view in the first place, a failed reshape can only be reported as an error rather than
+    /// worked around by copying. It's named with the `_raw` suffix (like [`Self::axis_iter_raw`])
+    /// rather than plain `reshape`, since that name is already taken by
+    /// [`ArrayBase::reshape`](crate::ArrayBase::reshape) (`ArcArray`-only, and panicking rather
+    /// than returning a `Result`).
+    ///
+    /// # Errors
+    ///
+    /// * [`ErrorKind::IncompatibleShape`] if `shape` doesn't have the same number of elements as
+    ///   this view.
+    /// * [`ErrorKind::IncompatibleLayout`] if this view is not in standard (C, row-major) layout.
+    ///   Unlike [`ArrayBase::into_shape`](crate::ArrayBase::into_shape), Fortran-layout views are
+    ///   not accepted, since this method always recomputes C-order strides for `shape`.
+    pub fn reshape_raw<D2>(self, shape: D2) -> Result<RawArrayView<A, D2>, ShapeError>
+    where
+        D2: Dimension,
+    {
+        if dimension::size_of_shape_checked(&shape) != Ok(self.dim.size()) {
+            return Err(error::incompatible_shapes(&self.dim, &shape));
+        }
+        if !self.is_standard_layout() {
+            return Err(error::from_kind(ErrorKind::IncompatibleLayout));
+        }
+        // safe because the view is standard layout and `shape` has the same length
+        unsafe { Ok(self.with_strides_dim(shape.default_strides(), shape)) }
+    }
+
+    /// Checks this view's strides and shape for overflow, and its pointer for alignment,
+    /// returning a descriptive error message if something looks wrong.
+    ///
+    /// Unlike the debug assertions in [`Self::from_shape_ptr`], this runs unconditionally,
+    /// regardless of whether debug assertions are enabled, which makes it useful for tests and
+    /// fuzzing harnesses that want to validate an already-constructed view. It does not (and
+    /// cannot) check that the view's elements are actually valid to read.
+    pub fn debug_validate(&self) -> Result<(), String> {
+        validate_raw_view_parts::<A, D>(&self.dim, &self.strides, self.ptr.as_ptr())
+    }
+
+    /// Returns whether this view's and `other`'s addressable memory overlap, so that a caller
+    /// can check for aliasing before deriving a mutable view from one of a pair of raw views
+    /// produced by FFI.
+    ///
+    /// This is a conservative bounding-box check: it computes each view's addressable `[start,
+    /// end)` byte range from its `ptr`/`dim`/`strides` and checks whether those ranges intersect.
+    /// For a strided view that doesn't touch every byte within its range, this can report `true`
+    /// even when no individual element actually aliases one of `other`'s, but it never reports
+    /// `false` for views that do alias. Two empty views never overlap.
+    ///
+    /// This is a pure arithmetic computation on the views' metadata: it doesn't dereference
+    /// either view's pointer, so it's safe to call even when the elements are not (yet) valid to
+    /// read.
+    pub fn overlaps_with(&self, other: &RawArrayView<A, D>) -> bool {
+        let (start1, end1) = addressable_byte_range(&self.dim, &self.strides, self.ptr.as_ptr());
+        let (start2, end2) = addressable_byte_range(&other.dim, &other.strides, other.ptr.as_ptr());
+        start1 < end2 && start2 < end1
+    }
+
+    /// Returns an iterator that traverses over `axis` and yields each raw subview along it, with
+    /// that axis removed.
+    ///
+    /// This mirrors [`ArrayBase::axis_iter`](crate::ArrayBase::axis_iter), but produces
+    /// `RawArrayView<A, D::Smaller>` without dereferencing any pointers, which makes it usable on
+    /// views whose elements are not (yet) valid to read.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn axis_iter_raw(self, axis: Axis) -> impl Iterator<Item = RawArrayView<A, D::Smaller>>
+    where
+        D: RemoveAxis,
+    {
+        let axis_len = self.len_of(axis);
+        let stride = stride_offset(1, self.strides.axis(axis));
+        let dim = self.dim.remove_axis(axis);
+        let strides = self.strides.remove_axis(axis);
+        let base_ptr = self.ptr.as_ptr();
+        (0..axis_len).map(move |i| {
+            let ptr = unsafe { base_ptr.offset(stride * i as isize) };
+            unsafe { RawArrayView::new_(ptr, dim.clone(), strides.clone()) }
+        })
+    }
+}
+
+impl<A> RawArrayView<A, Ix2> {
+    /// Return the diagonal of a 2D raw view as a one-dimensional raw view.
+    ///
+    /// The diagonal is the sequence indexed by *(0, 0)*, *(1, 1)*, etc. Its length is
+    /// `min(shape[0], shape[1])`, and it walks memory with stride `strides[0] + strides[1]`.
+    /// This is a pure pointer/stride computation, so it is always sound, even for views whose
+    /// elements are not (yet) valid to read.
+    pub fn diag_raw(self) -> RawArrayView<A, Ix1> {
+        let len = self.dim[0].min(self.dim[1]);
+        let stride = self.strides()[0] + self.strides()[1];
+        unsafe { self.with_strides_dim(Ix1(stride as Ix), Ix1(len)) }
+    }
+
+    /// Returns an iterator over all 1D lanes pointing in the direction of `axis`, each a raw view
+    /// of the elements obtained by holding every other axis fixed and varying `axis`.
+    ///
+    /// This mirrors [`ArrayBase::lanes`](crate::ArrayBase::lanes), but produces
+    /// `RawArrayView<A, Ix1>` without dereferencing any pointers, which makes it usable on views
+    /// whose elements are not (yet) valid to read. It is a pure pointer/stride computation, like
+    /// [`Self::diag_raw`].
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn lanes_raw(self, axis: Axis) -> impl Iterator<Item = RawArrayView<A, Ix1>>
+    {
+        let axis = axis.index();
+        let other = 1 - axis;
+        let lane_len = self.dim[axis];
+        let lane_stride = self.strides()[axis] as Ix;
+        let n_lanes = self.dim[other];
+        let outer_stride = self.strides()[other];
+        let base_ptr = self.ptr.as_ptr();
+        (0..n_lanes).map(move |i| {
+            let ptr = unsafe { base_ptr.offset(outer_stride * i as isize) };
+            unsafe { RawArrayView::new_(ptr, Ix1(lane_len), Ix1(lane_stride)) }
+        })
+    }
 }
 
 impl<T, D> RawArrayView<Complex<T>, D>
@@ -217,6 +492,62 @@ where
     }
 }
 
+impl<T, D> RawArrayView<T, D>
+where
+    D: Dimension,
+{
+    /// Attempts to reconstruct a `RawArrayView<Complex<T>, D>` from the `re`/`im` views produced
+    /// by a prior call to [`RawArrayView::<Complex<T>, D>::split_complex`].
+    ///
+    /// Returns `None` if `re` and `im` don't have matching shape and strides, or if `im`'s
+    /// pointer is not exactly one `T` past `re`'s, since either would mean the two views could
+    /// not have come from splitting the same `Complex<T>` view.
+    pub fn join_complex(re: Self, im: Self) -> Option<RawArrayView<Complex<T>, D>> {
+        // Check that the size and alignment of `Complex<T>` are as expected.
+        // These assertions should always pass, for arbitrary `T`.
+        assert_eq!(
+            mem::size_of::<Complex<T>>(),
+            mem::size_of::<T>().checked_mul(2).unwrap()
+        );
+        assert_eq!(mem::align_of::<Complex<T>>(), mem::align_of::<T>());
+
+        if re.dim != im.dim || re.strides != im.strides {
+            return None;
+        }
+
+        if mem::size_of::<T>() != 0 && !re.is_empty() {
+            // SAFETY: `split_complex` never offsets `re`'s pointer by more than one `T` to
+            // produce `im`, so this is always in bounds when `re` did come from a split.
+            if unsafe { re.ptr.as_ptr().add(1) } != im.ptr.as_ptr() {
+                return None;
+            }
+        } else if re.ptr != im.ptr {
+            // In the zero-sized-element or empty case, `split_complex` leaves `im`'s pointer
+            // equal to `re`'s rather than offsetting it.
+            return None;
+        }
+
+        let dim = re.dim.clone();
+
+        // Halve the strides back down, undoing `split_complex`'s doubling. As there, axes of
+        // length <= 1 are left as-is, since `split_complex` never touched them either.
+        let mut strides = re.strides.clone();
+        if mem::size_of::<T>() != 0 {
+            for ax in 0..strides.ndim() {
+                if dim[ax] > 1 {
+                    if strides[ax] % 2 != 0 {
+                        return None;
+                    }
+                    strides[ax] /= 2;
+                }
+            }
+        }
+
+        let ptr: *mut Complex<T> = re.ptr.as_ptr().cast();
+        Some(unsafe { RawArrayView::new_(ptr, dim, strides) })
+    }
+}
+
 impl<A, D> RawArrayViewMut<A, D>
 where
     D: Dimension,
@@ -334,6 +665,63 @@ where
         ArrayViewMut::new(self.ptr, self.dim, self.strides)
     }
 
+    /// Writes `f(index)` to every element of this view, honoring its strides.
+    ///
+    /// This is meant for FFI kernels that need to fully initialize a raw view backed by
+    /// possibly-uninitialized memory, where going through a safe `ArrayViewMut` first isn't an
+    /// option because that already requires every element to be initialized and valid to read.
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the same pointer validity requirements as
+    /// [`Self::deref_into_view_mut`], except that the pointed-to memory need not already hold
+    /// valid `A` values, since this only ever writes to it and never reads.
+    pub unsafe fn write_all<F>(&mut self, mut f: F)
+    where
+        F: FnMut(D) -> A,
+    {
+        let mut index = match self.dim.first_index() {
+            Some(index) => index,
+            None => return,
+        };
+        loop {
+            let offset = D::stride_offset(&index, &self.strides);
+            self.ptr.as_ptr().offset(offset).write(f(index.clone()));
+            match self.dim.next_for(index) {
+                Some(next) => index = next,
+                None => break,
+            }
+        }
+    }
+
+    /// Combines the elements of this view with `other`'s, calling `f(self_ptr, other_ptr)` for
+    /// each pair of corresponding element pointers.
+    ///
+    /// This is meant for FFI kernels combining two raw views of identical shape (e.g. a
+    /// SAXPY-like loop), where constructing safe views first isn't necessary just to walk both
+    /// element sequences in lockstep.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` and `other` do not have the same shape.
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the same pointer validity requirements as
+    /// [`Self::deref_into_view_mut`] for `self`'s elements, and the same requirements as
+    /// [`RawArrayView::deref_into_view`] for `other`'s.
+    pub unsafe fn zip_with<B, F>(&mut self, other: &RawArrayView<B, D>, mut f: F)
+    where
+        F: FnMut(*mut A, *const B),
+    {
+        assert_eq!(self.dim, other.dim, "arrays must have the same shape");
+        let self_iter = Baseiter::new(self.ptr.as_ptr(), self.dim.clone(), self.strides.clone());
+        let other_iter = Baseiter::new(other.ptr.as_ptr(), other.dim.clone(), other.strides.clone());
+        for (self_ptr, other_ptr) in self_iter.zip(other_iter) {
+            f(self_ptr, other_ptr as *const B);
+        }
+    }
+
     /// Split the array view along `axis` and return one array pointer strictly
     /// before the split and one array pointer after the split.
     ///
@@ -367,6 +755,58 @@ where
         let ptr = self.ptr.cast::<B>();
         unsafe { RawArrayViewMut::new(ptr, self.dim, self.strides) }
     }
+
+    /// Checks this view's strides and shape for overflow, and its pointer for alignment,
+    /// returning a descriptive error message if something looks wrong.
+    ///
+    /// See [`RawArrayView::debug_validate`] for details.
+    pub fn debug_validate(&self) -> Result<(), String> {
+        validate_raw_view_parts::<A, D>(&self.dim, &self.strides, self.ptr.as_ptr())
+    }
+
+    /// Returns an iterator that traverses over `axis` and yields each mutable raw subview along
+    /// it, with that axis removed.
+    ///
+    /// See [`RawArrayView::axis_iter_raw`] for details.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn axis_iter_raw(self, axis: Axis) -> impl Iterator<Item = RawArrayViewMut<A, D::Smaller>>
+    where
+        D: RemoveAxis,
+    {
+        let axis_len = self.len_of(axis);
+        let stride = stride_offset(1, self.strides.axis(axis));
+        let dim = self.dim.remove_axis(axis);
+        let strides = self.strides.remove_axis(axis);
+        let base_ptr = self.ptr.as_ptr();
+        (0..axis_len).map(move |i| {
+            let ptr = unsafe { base_ptr.offset(stride * i as isize) };
+            unsafe { RawArrayViewMut::new_(ptr, dim.clone(), strides.clone()) }
+        })
+    }
+
+    /// Reinterprets this view's elements as `MaybeUninit<A>`, so that they can be written to
+    /// without requiring their prior contents to be valid.
+    ///
+    /// This is a pure pointer reinterpretation: `A` and `MaybeUninit<A>` have the same size,
+    /// alignment, and representation, so no data is moved or otherwise touched.
+    pub fn as_uninit(self) -> RawArrayViewMut<MaybeUninit<A>, D> {
+        self.cast::<MaybeUninit<A>>()
+    }
+}
+
+impl<A> RawArrayViewMut<A, Ix2> {
+    /// Return the diagonal of a 2D raw view as a one-dimensional raw view.
+    ///
+    /// The diagonal is the sequence indexed by *(0, 0)*, *(1, 1)*, etc. Its length is
+    /// `min(shape[0], shape[1])`, and it walks memory with stride `strides[0] + strides[1]`.
+    /// This is a pure pointer/stride computation, so it is always sound, even for views whose
+    /// elements are not (yet) valid to read.
+    pub fn diag_raw(self) -> RawArrayViewMut<A, Ix1> {
+        let len = self.dim[0].min(self.dim[1]);
+        let stride = self.strides()[0] + self.strides()[1];
+        unsafe { self.with_strides_dim(Ix1(stride as Ix), Ix1(len)) }
+    }
 }
 
 impl<T, D> RawArrayViewMut<Complex<T>, D>
@@ -166,6 +166,8 @@ mod itertools;
 mod argument_traits;
 #[cfg(feature = "serde")]
 mod array_serde;
+#[cfg(feature = "serde")]
+pub use crate::array_serde::NdArrayMsg;
 mod arrayformat;
 mod arraytraits;
 pub use crate::argument_traits::AssignElem;
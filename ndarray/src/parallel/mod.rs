@@ -147,9 +147,11 @@ pub mod prelude {
 
 pub use self::par::Parallel;
 pub use crate::par_azip;
+pub use self::raw::{RawArrayViewMutChunk, RawAxisChunksIterMut};
 
 mod impl_par_methods;
 mod into_impls;
 mod par;
+mod raw;
 mod send_producer;
 mod zipmacro;
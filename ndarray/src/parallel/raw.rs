@@ -0,0 +1,177 @@
+use rayon::iter::plumbing::{bridge, Producer, ProducerCallback};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+
+use crate::{Axis, Dimension, RawArrayViewMut};
+
+/// # Parallel methods
+///
+/// These methods require crate feature `rayon`.
+impl<A, D> RawArrayViewMut<A, D>
+where
+    D: Dimension,
+{
+    /// Returns a parallel iterator of non-overlapping chunks of size 1 along `axis`, as raw
+    /// mutable views.
+    ///
+    /// Unlike [`ArrayBase::axis_chunks_iter_mut`](crate::ArrayBase::axis_chunks_iter_mut), this
+    /// works directly on raw pointers, so it requires only `A: Send` (not `A: Sync`) and can be used
+    /// to hand off chunks to FFI-backed kernels that manage their own thread safety. Building the
+    /// iterator itself is safe (each chunk is produced by repeatedly calling
+    /// [`split_at`](Self::split_at), so chunks never overlap), but *dereferencing* the raw views
+    /// inside each chunk, as with any raw view, requires `unsafe`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn axis_chunks_par_iter_mut(self, axis: Axis) -> RawAxisChunksIterMut<A, D> {
+        RawAxisChunksIterMut::new(self, axis)
+    }
+}
+
+/// A `RawArrayViewMut` chunk handed out by [`RawArrayViewMut::axis_chunks_par_iter_mut`].
+///
+/// `RawArrayViewMut` is not `Send` on its own, since it is built from a raw pointer with no
+/// borrow checking to prove that chunks handed to different threads don't alias. This wrapper
+/// asserts that they don't: [`axis_chunks_par_iter_mut`](RawArrayViewMut::axis_chunks_par_iter_mut)
+/// only ever produces non-overlapping chunks by repeatedly splitting along `axis`, so it is safe
+/// to send each chunk to a different thread as long as the caller doesn't otherwise alias the
+/// pointers involved (e.g. through a view constructed independently of this iterator).
+#[repr(transparent)]
+pub struct RawArrayViewMutChunk<A, D>(RawArrayViewMut<A, D>);
+
+unsafe impl<A, D> Send for RawArrayViewMutChunk<A, D> {}
+
+impl<A, D> RawArrayViewMutChunk<A, D> {
+    /// Unwraps the chunk, yielding back the `RawArrayViewMut`.
+    pub fn into_raw_view_mut(self) -> RawArrayViewMut<A, D> {
+        self.0
+    }
+}
+
+/// A rayon `IndexedParallelIterator` over non-overlapping [`RawArrayViewMutChunk`]s of a
+/// `RawArrayViewMut`, split along one axis. See [`RawArrayViewMut::axis_chunks_par_iter_mut`].
+pub struct RawAxisChunksIterMut<A, D> {
+    view: RawArrayViewMut<A, D>,
+    axis: Axis,
+}
+
+// Safe for the same reason as `RawArrayViewMutChunk`: the chunks split off of `view` as this
+// iterator is driven never overlap, so handing them to other threads cannot cause data races.
+unsafe impl<A, D> Send for RawAxisChunksIterMut<A, D> {}
+
+impl<A, D: Dimension> RawAxisChunksIterMut<A, D> {
+    pub(crate) fn new(view: RawArrayViewMut<A, D>, axis: Axis) -> Self {
+        Self { view, axis }
+    }
+}
+
+impl<A, D: Dimension> ParallelIterator for RawAxisChunksIterMut<A, D>
+where
+    A: Send,
+{
+    type Item = RawArrayViewMutChunk<A, D>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.view.len_of(self.axis))
+    }
+}
+
+impl<A, D: Dimension> IndexedParallelIterator for RawAxisChunksIterMut<A, D>
+where
+    A: Send,
+{
+    fn len(&self) -> usize {
+        self.view.len_of(self.axis)
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<Cb>(self, callback: Cb) -> Cb::Output
+    where
+        Cb: ProducerCallback<Self::Item>,
+    {
+        callback.callback(RawArrayViewMutProducer { view: self.view, axis: self.axis })
+    }
+}
+
+struct RawArrayViewMutProducer<A, D> {
+    view: RawArrayViewMut<A, D>,
+    axis: Axis,
+}
+
+unsafe impl<A, D> Send for RawArrayViewMutProducer<A, D> {}
+
+impl<A, D: Dimension> Producer for RawArrayViewMutProducer<A, D>
+where
+    A: Send,
+{
+    type Item = RawArrayViewMutChunk<A, D>;
+    type IntoIter = RawArrayViewMutChunkIter<A, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RawArrayViewMutChunkIter { view: Some(self.view), axis: self.axis }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (a, b) = self.view.split_at(self.axis, index);
+        (Self { view: a, axis: self.axis }, Self { view: b, axis: self.axis })
+    }
+}
+
+/// Yields the one-element-along-`axis` chunks of a `RawArrayViewMut`, in order, by repeated
+/// `RawArrayViewMut::split_at`.
+pub struct RawArrayViewMutChunkIter<A, D> {
+    view: Option<RawArrayViewMut<A, D>>,
+    axis: Axis,
+}
+
+// See the `Send` impl on `RawAxisChunksIterMut` above.
+unsafe impl<A, D> Send for RawArrayViewMutChunkIter<A, D> {}
+
+impl<A, D: Dimension> Iterator for RawArrayViewMutChunkIter<A, D> {
+    type Item = RawArrayViewMutChunk<A, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let view = self.view.take()?;
+        if view.len_of(self.axis) == 0 {
+            return None;
+        }
+        let (head, tail) = view.split_at(self.axis, 1);
+        self.view = Some(tail);
+        Some(RawArrayViewMutChunk(head))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<A, D: Dimension> DoubleEndedIterator for RawArrayViewMutChunkIter<A, D> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let view = self.view.take()?;
+        let len = view.len_of(self.axis);
+        if len == 0 {
+            return None;
+        }
+        let (head, tail) = view.split_at(self.axis, len - 1);
+        self.view = Some(head);
+        Some(RawArrayViewMutChunk(tail))
+    }
+}
+
+impl<A, D: Dimension> ExactSizeIterator for RawArrayViewMutChunkIter<A, D> {
+    fn len(&self) -> usize {
+        self.view.as_ref().map_or(0, |v| v.len_of(self.axis))
+    }
+}
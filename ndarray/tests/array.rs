@@ -13,9 +13,10 @@ use itertools::{zip, Itertools};
 use ndarray::prelude::*;
 use ndarray::{arr3, rcarr2};
 use ndarray::indices;
-use ndarray::{Slice, SliceInfo, SliceInfoElem};
+use ndarray::{ErrorKind, Slice, SliceInfo, SliceInfoElem};
 use num_complex::Complex;
 use std::convert::TryFrom;
+use std::mem::MaybeUninit;
 
 macro_rules! assert_panics {
     ($body:expr) => {
@@ -1756,6 +1757,21 @@ fn split_at() {
     assert_eq!(right.shape(), [3, 0, 5]);
 }
 
+#[test]
+fn split_at_1d_view_mut_allows_disjoint_simultaneous_mutation() {
+    let mut a = Array1::from(vec![0, 1, 2, 3, 4]);
+
+    let (mut left, mut right) = a.view_mut().split_at(Axis(0), 2);
+    for x in left.iter_mut() {
+        *x += 10;
+    }
+    for x in right.iter_mut() {
+        *x += 100;
+    }
+
+    assert_eq!(a, Array1::from(vec![10, 11, 102, 103, 104]));
+}
+
 #[test]
 #[should_panic]
 fn deny_split_at_axis_out_of_bounds() {
@@ -2066,6 +2082,16 @@ fn test_view_from_shape_ptr() {
     assert_eq!(view, aview2(&[[0, 0, 2], [3, 4, 6]]));
 }
 
+#[test]
+fn test_view_from_shape_ptr_over_vec_buffer_with_explicit_strides() {
+    // Mimics ingesting a buffer handed over by numpy: a `Vec` owned elsewhere, plus
+    // explicit C-order strides (in units of elements, i.e. numpy's byte strides already
+    // divided by the element size).
+    let data: Vec<i32> = (0..6).collect();
+    let view = unsafe { ArrayView::from_shape_ptr((2, 3).strides((3, 1)), data.as_ptr()) };
+    assert_eq!(view, aview2(&[[0, 1, 2], [3, 4, 5]]));
+}
+
 #[should_panic(expected = "Unsupported")]
 #[cfg(debug_assertions)]
 #[test]
@@ -2607,3 +2633,397 @@ fn test_split_complex_invert_axis() {
     assert_eq!(cmplx.re, a.mapv(|z| z.re));
     assert_eq!(cmplx.im, a.mapv(|z| z.im));
 }
+
+#[test]
+fn test_join_complex_view_roundtrip() {
+    let a = Array3::from_shape_fn((3, 4, 5), |(i, j, k)| {
+        Complex::new(i as f64 * j as f64, k as f64)
+    });
+    let raw = a.view().raw_view();
+    let Complex { re, im } = raw.split_complex();
+    let joined = RawArrayView::join_complex(re, im).unwrap();
+    unsafe {
+        assert_eq!(joined.deref_into_view(), a.view());
+    }
+}
+
+#[test]
+fn test_join_complex_rejects_mismatched_views() {
+    let a = Array3::from_shape_fn((3, 4, 5), |(i, j, k)| {
+        Complex::new(i as f64 * j as f64, k as f64)
+    });
+    let b = Array3::from_shape_fn((3, 4, 5), |(i, j, k)| {
+        Complex::new(k as f64, i as f64 * j as f64)
+    });
+
+    let re = a.view().raw_view().split_complex().re;
+    let im = b.view().raw_view().split_complex().im;
+    assert!(RawArrayView::join_complex(re, im).is_none());
+
+    // Shape mismatch is also rejected.
+    let c = Array3::from_shape_fn((3, 4, 4), |(i, j, k)| {
+        Complex::new(i as f64 * j as f64, k as f64)
+    });
+    let re = a.view().raw_view().split_complex().re;
+    let im = c.view().raw_view().split_complex().im;
+    assert!(RawArrayView::join_complex(re, im).is_none());
+}
+
+#[test]
+fn test_raw_view_broadcast_1x3_to_4x3() {
+    let a = Array2::from_shape_fn((1, 3), |(_, j)| j);
+    let broadcast = a.view().raw_view().broadcast_raw(Ix2(4, 3)).unwrap();
+    let view = unsafe { broadcast.deref_into_view() };
+    for i in 0..4 {
+        assert_eq!(view.row(i), a.row(0));
+    }
+}
+
+#[test]
+fn test_raw_view_broadcast_rejects_incompatible_shape() {
+    let a = Array2::from_shape_fn((2, 3), |(i, j)| i * 3 + j);
+    assert!(a.view().raw_view().broadcast_raw(Ix2(4, 3)).is_none());
+}
+
+#[test]
+fn test_raw_view_mut_write_all() {
+    let mut a = Array2::<usize>::uninit((3, 3));
+    let mut raw = a.raw_view_mut().cast::<usize>();
+    unsafe {
+        raw.write_all(|ix: Ix2| ix[0] * 3 + ix[1]);
+        let view = raw.deref_into_view();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_eq!(view[(i, j)], i * 3 + j);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_raw_view_copy_to_contiguous_transposed() {
+    let a = Array2::from_shape_fn((2, 3), |(i, j)| i * 3 + j);
+    let transposed = a.view().reversed_axes();
+    let raw = transposed.raw_view();
+
+    let mut dst = vec![0usize; raw.len()];
+    unsafe {
+        raw.copy_to_contiguous(dst.as_mut_ptr());
+    }
+
+    assert_eq!(dst, vec![0, 3, 1, 4, 2, 5]);
+}
+
+#[test]
+fn test_raw_view_mut_zip_with() {
+    let mut a = Array2::from_shape_fn((2, 2), |(i, j)| i * 2 + j);
+    let b = Array2::from_shape_fn((2, 2), |(i, j)| 10 * (i * 2 + j));
+
+    let mut raw_a = a.raw_view_mut();
+    let raw_b = b.raw_view();
+    unsafe {
+        raw_a.zip_with(&raw_b, |a_ptr, b_ptr| *a_ptr += *b_ptr);
+    }
+
+    assert_eq!(a, array![[0, 11], [22, 33]]);
+}
+
+#[test]
+fn test_raw_view_from_shape_ptr_bytes() {
+    // A 2x3 row-major f64 buffer, with strides expressed in bytes as numpy would report them.
+    let data = [1.0f64, 2., 3., 4., 5., 6.];
+    let elem_size = std::mem::size_of::<f64>();
+    let byte_strides = (3 * elem_size, elem_size);
+
+    unsafe {
+        let raw = RawArrayView::from_shape_ptr_bytes((2, 3), byte_strides, data.as_ptr());
+        let view = raw.deref_into_view();
+        assert_eq!(view, array![[1., 2., 3.], [4., 5., 6.]]);
+    }
+}
+
+#[test]
+fn test_raw_view_into_dimensionality() {
+    let a = Array2::<f64>::zeros((2, 3));
+    let raw = a.raw_view().into_dyn();
+
+    let raw2: RawArrayView<f64, Ix2> = raw.into_dimensionality::<Ix2>().unwrap();
+    unsafe {
+        assert_eq!(raw2.deref_into_view(), a.view());
+    }
+}
+
+#[test]
+fn test_raw_view_into_dimensionality_rank_mismatch() {
+    let a = Array2::<f64>::zeros((2, 3));
+    let raw = a.raw_view().into_dyn();
+
+    assert!(raw.into_dimensionality::<Ix3>().is_err());
+}
+
+#[test]
+fn test_raw_view_ndim_and_raw_dim() {
+    let mut a = Array3::<f64>::zeros((2, 3, 4));
+
+    let raw = a.raw_view();
+    assert_eq!(raw.ndim(), 3);
+    assert_eq!(raw.raw_dim(), Ix3(2, 3, 4));
+
+    let raw_mut = a.raw_view_mut();
+    assert_eq!(raw_mut.ndim(), 3);
+    assert_eq!(raw_mut.raw_dim(), Ix3(2, 3, 4));
+}
+
+#[test]
+fn test_raw_view_debug_validate_accepts_well_formed_view() {
+    let a = Array2::<f64>::zeros((2, 3));
+    assert!(a.raw_view().debug_validate().is_ok());
+}
+
+#[test]
+fn test_raw_view_debug_validate_rejects_overflowing_shape() {
+    let a = Array2::<f64>::zeros((1, 1));
+
+    // Broadcasting doesn't validate the resulting shape, so this is a way to reach an
+    // otherwise-unreachable-by-construction view whose element count overflows `isize::MAX`.
+    let huge = a.raw_view().broadcast_raw(Ix2(isize::MAX as usize, 2)).unwrap();
+
+    let err = huge.debug_validate().unwrap_err();
+    assert!(err.contains("Overflow"), "unexpected message: {}", err);
+}
+
+#[test]
+fn test_raw_view_axis_iter_raw_yields_lower_dimensional_subviews() {
+    let a = Array3::<f64>::from_shape_fn((3, 2, 2), |(i, j, k)| (i * 4 + j * 2 + k) as f64);
+
+    let subviews: Vec<_> = a.raw_view().axis_iter_raw(Axis(0)).collect();
+    assert_eq!(subviews.len(), 3);
+
+    for (i, view) in subviews.into_iter().enumerate() {
+        assert_eq!(view.raw_dim(), Ix2(2, 2));
+        let expected = a.index_axis(Axis(0), i);
+        // SAFETY: `view` was derived from `a`, whose elements are all valid to read.
+        let actual = unsafe { view.deref_into_view() };
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_raw_view_len_of_and_stride_of_transposed_axes() {
+    let a = Array2::<f64>::zeros((2, 3));
+    let raw = a.raw_view().reversed_axes();
+
+    assert_eq!(raw.len_of(Axis(0)), 3);
+    assert_eq!(raw.len_of(Axis(1)), 2);
+    assert_eq!(raw.stride_of(Axis(0)), a.stride_of(Axis(1)));
+    assert_eq!(raw.stride_of(Axis(1)), a.stride_of(Axis(0)));
+
+    let mut b = Array2::<f64>::zeros((2, 3));
+    let raw_mut = b.raw_view_mut().reversed_axes();
+
+    assert_eq!(raw_mut.len_of(Axis(0)), 3);
+    assert_eq!(raw_mut.len_of(Axis(1)), 2);
+    assert_eq!(raw_mut.stride_of(Axis(0)), b.stride_of(Axis(1)));
+    assert_eq!(raw_mut.stride_of(Axis(1)), b.stride_of(Axis(0)));
+}
+
+#[test]
+fn test_raw_array_view_mut_as_uninit_then_assume_init() {
+    let mut a: Array2<i32> = Array2::zeros((2, 3));
+
+    let mut uninit = a.raw_view_mut().as_uninit();
+    // SAFETY: `uninit` points into `a`'s own allocation, which is valid for writes of `i32`.
+    unsafe {
+        uninit.write_all(|ix: Ix2| MaybeUninit::new((ix[0] * 3 + ix[1]) as i32));
+    }
+    // SAFETY: every element was just written above.
+    let initialized = unsafe { uninit.assume_init().deref_into_view() };
+    assert_eq!(initialized, array![[0, 1, 2], [3, 4, 5]]);
+}
+
+#[test]
+fn test_raw_view_diag_raw_square() {
+    let a = Array2::from_shape_fn((3, 3), |(i, j)| i * 3 + j);
+
+    let diag = a.raw_view().diag_raw();
+    assert_eq!(diag.len(), 3);
+    let diag = unsafe { diag.deref_into_view() };
+    assert_eq!(diag, array![0, 4, 8]);
+
+    let mut b = a.clone();
+    let diag_mut = b.raw_view_mut().diag_raw();
+    assert_eq!(diag_mut.len(), 3);
+    let diag_mut = unsafe { diag_mut.deref_into_view() };
+    assert_eq!(diag_mut, array![0, 4, 8]);
+}
+
+#[test]
+fn test_raw_view_diag_raw_non_square() {
+    let a = Array2::from_shape_fn((2, 4), |(i, j)| i * 4 + j);
+
+    let diag = a.raw_view().diag_raw();
+    assert_eq!(diag.len(), 2);
+    let diag = unsafe { diag.deref_into_view() };
+    assert_eq!(diag, array![0, 5]);
+}
+
+#[test]
+fn test_raw_view_cast_with_axis_widens_length() {
+    let a: Array1<u32> = array![0x04030201u32, 0x08070605u32];
+
+    let bytes = a.raw_view().cast_with_axis::<u8>(Axis(0), 4);
+    assert_eq!(bytes.raw_dim(), Ix1(8));
+    // SAFETY: `a`'s elements are valid `u32`s, so reinterpreting them as 4x as many `u8`s is
+    // sound, and `a` outlives `bytes`.
+    let actual = unsafe { bytes.deref_into_view() };
+    assert_eq!(actual, array![1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+#[should_panic(expected = "cast_with_axis requires the view to be contiguous along `axis`")]
+fn test_raw_view_cast_with_axis_panics_on_non_contiguous_axis() {
+    let a: Array2<u32> = Array2::zeros((2, 4));
+    let strided = a.raw_view().reversed_axes();
+
+    let _ = strided.cast_with_axis::<u8>(Axis(1), 4);
+}
+
+#[test]
+fn test_raw_view_lanes_raw_columns_of_2x3() {
+    let a = Array2::from_shape_fn((2, 3), |(i, j)| i * 3 + j);
+
+    let lanes: Vec<_> = a.raw_view().lanes_raw(Axis(0)).collect();
+    assert_eq!(lanes.len(), 3);
+
+    for (j, lane) in lanes.into_iter().enumerate() {
+        assert_eq!(lane.raw_dim(), Ix1(2));
+        assert_eq!(lane.stride_of(Axis(0)), a.stride_of(Axis(0)));
+        // SAFETY: `lane` was derived from `a`, whose elements are all valid to read.
+        let actual = unsafe { lane.deref_into_view() };
+        assert_eq!(actual, a.column(j));
+    }
+}
+
+#[test]
+fn test_raw_view_reshape_raw_to_1d_and_2d() {
+    let a = Array2::from_shape_fn((2, 3), |(i, j)| i * 3 + j);
+
+    let flat = a.raw_view().reshape_raw(Ix1(6)).unwrap();
+    assert_eq!(flat.raw_dim(), Ix1(6));
+    // SAFETY: `flat` was derived from `a`, whose elements are all valid to read.
+    let actual = unsafe { flat.deref_into_view() };
+    assert_eq!(actual, array![0, 1, 2, 3, 4, 5]);
+
+    let reshaped = a.raw_view().reshape_raw(Ix2(3, 2)).unwrap();
+    assert_eq!(reshaped.raw_dim(), Ix2(3, 2));
+    // SAFETY: `reshaped` was derived from `a`, whose elements are all valid to read.
+    let actual = unsafe { reshaped.deref_into_view() };
+    assert_eq!(actual, array![[0, 1], [2, 3], [4, 5]]);
+}
+
+#[test]
+fn test_raw_view_reshape_raw_errors_on_non_contiguous_view() {
+    let a: Array2<u32> = Array2::zeros((2, 4));
+    let strided = a.raw_view().reversed_axes();
+
+    match strided.reshape_raw(Ix1(8)) {
+        Ok(_) => panic!("expected reshape_raw to fail on a non-contiguous view"),
+        Err(err) => assert_eq!(err.kind(), ErrorKind::IncompatibleLayout),
+    }
+}
+
+#[test]
+fn test_raw_view_reshape_raw_errors_on_mismatched_len() {
+    let a = Array2::<u32>::zeros((2, 3));
+
+    match a.raw_view().reshape_raw(Ix1(5)) {
+        Ok(_) => panic!("expected reshape_raw to fail on a mismatched length"),
+        Err(err) => assert_eq!(err.kind(), ErrorKind::IncompatibleShape),
+    }
+}
+
+#[test]
+fn test_raw_view_overlaps_with_identical_view() {
+    let a = Array2::<u32>::zeros((2, 3));
+    let view = a.raw_view();
+    assert!(view.overlaps_with(&view));
+}
+
+#[test]
+fn test_raw_view_overlaps_with_disjoint_views() {
+    let a = Array1::<u32>::zeros(10);
+    let (left, right) = a.raw_view().split_at(Axis(0), 5);
+    assert!(!left.overlaps_with(&right));
+    assert!(!right.overlaps_with(&left));
+}
+
+#[test]
+fn test_raw_view_overlaps_with_overlapping_subviews() {
+    let a = Array1::<u32>::zeros(10);
+    // `[0, 6)` and `[4, 10)` share elements 4 and 5.
+    let (first, _) = a.raw_view().split_at(Axis(0), 6);
+    let (_, second) = a.raw_view().split_at(Axis(0), 4);
+    assert!(first.overlaps_with(&second));
+    assert!(second.overlaps_with(&first));
+}
+
+#[test]
+fn test_raw_view_overlaps_with_disjoint_strided_views() {
+    let a = Array1::<u32>::zeros(40);
+    // Every-other-element slices of two far-apart, non-overlapping ranges: still disjoint even
+    // though each view is itself strided.
+    let front = a.slice(s![0..10;2]);
+    let back = a.slice(s![20..30;2]);
+    let front = front.raw_view();
+    let back = back.raw_view();
+    assert!(!front.overlaps_with(&back));
+    assert!(!back.overlaps_with(&front));
+}
+
+#[test]
+fn test_raw_view_overlaps_with_overlapping_strided_views() {
+    let a = Array2::<u32>::zeros((4, 4));
+    // The main diagonal and the first column share their first element, (0, 0).
+    let diag = a.raw_view().diag_raw();
+    let col0 = a.raw_view().lanes_raw(Axis(0)).next().unwrap();
+    assert!(diag.overlaps_with(&col0));
+    assert!(col0.overlaps_with(&diag));
+}
+
+#[test]
+fn test_raw_view_overlaps_with_empty_view_never_overlaps() {
+    let a = Array1::<u32>::zeros(10);
+    let empty = a.raw_view().split_at(Axis(0), 0).0;
+    assert!(!empty.overlaps_with(&a.raw_view()));
+    assert!(!empty.overlaps_with(&empty));
+}
+
+#[test]
+fn test_raw_view_overlaps_with_negative_stride_view() {
+    let a = Array1::<u32>::zeros(10);
+    // A negative-step slice walks from the last element backward, so its addressable range
+    // extends *below* its own `ptr` rather than only forward from it.
+    let reversed = a.slice(s![..;-1]).raw_view();
+    assert!(reversed.overlaps_with(&a.raw_view()));
+    assert!(a.raw_view().overlaps_with(&reversed));
+
+    let (front, back) = a.raw_view().split_at(Axis(0), 5);
+    let reversed_front = a.slice(s![..5;-1]).raw_view();
+    assert!(reversed_front.overlaps_with(&front));
+    assert!(!reversed_front.overlaps_with(&back));
+}
+
+#[test]
+fn test_flipped_1d_view() {
+    let a = Array1::from(vec![0, 1, 2, 3, 4]);
+    let flipped = a.view().flipped(Axis(0));
+    assert_eq!(flipped, Array1::from(vec![4, 3, 2, 1, 0]));
+}
+
+#[test]
+fn test_flipped_2d_view_mut() {
+    let mut a = Array2::from_shape_fn((2, 5), |(i, j)| i * 5 + j);
+    let expected = array![[4, 3, 2, 1, 0], [9, 8, 7, 6, 5]];
+    let flipped = a.view_mut().flipped(Axis(1));
+    assert_eq!(flipped, expected);
+}
@@ -72,6 +72,18 @@ fn test_axis_chunks_iter() {
     assert_eq!(s, a.sum());
 }
 
+#[test]
+fn test_raw_axis_chunks_iter_mut() {
+    let mut a = Array2::<f64>::zeros((M, N));
+    a.raw_view_mut()
+        .axis_chunks_par_iter_mut(Axis(0))
+        .for_each(|chunk| {
+            let mut view = unsafe { chunk.into_raw_view_mut().deref_into_view_mut() };
+            view.fill(1.);
+        });
+    assert_eq!(a.sum(), (M * N) as f64);
+}
+
 #[test]
 #[cfg(feature = "approx")]
 fn test_axis_chunks_iter_mut() {
@@ -96,3 +96,86 @@ fn raw_view_negative_strides() {
     let data: [u16; 2] = [0x0011, 0x2233];
     misaligned_deref(&data);
 }
+
+#[test]
+fn raw_view_from_shape_ptr_zst_split() {
+    // Zero-sized elements never move the pointer: `from_shape_ptr`'s docs already allow a
+    // dangling-but-aligned pointer in this case, and `split_at`'s offset arithmetic moves
+    // the pointer by zero bytes for a zero-sized element. `NonNull::dangling()` gives a well-aligned pointer
+    // that's valid to use here without ever being read through.
+    let ptr = std::ptr::NonNull::<()>::dangling().as_ptr();
+    let view = unsafe { RawArrayView::from_shape_ptr((3, 3), ptr) };
+    assert_eq!(view.len(), 9);
+    assert_eq!(view.shape(), &[3, 3]);
+
+    let (left, right) = view.split_at(Axis(0), 1);
+    assert_eq!(left.len(), 3);
+    assert_eq!(left.shape(), &[1, 3]);
+    assert_eq!(right.len(), 6);
+    assert_eq!(right.shape(), &[2, 3]);
+    // Every pointer stays the same dangling-but-aligned address, since offsetting a pointer
+    // to a zero-sized element by any stride moves it by zero bytes.
+    assert_eq!(left.as_ptr(), ptr);
+    assert_eq!(right.as_ptr(), ptr);
+
+    // `split_complex` only applies to `RawArrayView<Complex<T>, D>`, so it has no
+    // zero-sized-element counterpart here; `cast` already covers reinterpreting a raw
+    // view's element type (see `raw_view_cast_zst` above).
+}
+
+#[test]
+fn raw_view_as_ptr_matches_construction_pointer() {
+    // `as_ptr`/`as_mut_ptr` are inherited from `ArrayBase` for any `RawData`/`RawDataMut`, so raw
+    // views already expose the base element pointer on their public surface without needing an
+    // `unsafe` `deref_into_view` first.
+    let data = [1i32, 2, 3, 4];
+
+    let view = unsafe { RawArrayView::from_shape_ptr(4, data.as_ptr()) };
+    assert_eq!(view.as_ptr(), data.as_ptr());
+
+    let mut data = data;
+    let ptr = data.as_mut_ptr();
+    let mut view_mut = unsafe { RawArrayViewMut::from_shape_ptr(4, ptr) };
+    assert_eq!(view_mut.as_ptr(), ptr as *const i32);
+    assert_eq!(view_mut.as_mut_ptr(), ptr);
+}
+
+#[test]
+fn raw_view_mut_raw_view_borrows_immutably_without_giving_up_the_mutable_original() {
+    // `raw_view(&self)` is inherited from `ArrayBase` for any `RawData`, so `RawArrayViewMut`
+    // already gets a way to borrow a temporary immutable `RawArrayView` over the same data,
+    // tied to `&self`, without consuming the mutable view the way `into_raw_view` does.
+    let mut data = [1i32, 2, 3, 4];
+    let mut view_mut = unsafe { RawArrayViewMut::from_shape_ptr(4, data.as_mut_ptr()) };
+
+    let immut = view_mut.raw_view();
+    assert_eq!(unsafe { *immut.get_ptr(0).unwrap() }, 1);
+    assert_eq!(immut.as_ptr(), data.as_ptr());
+
+    // The mutable original is still usable after the immutable borrow above ends.
+    unsafe { *view_mut.get_mut_ptr(0).unwrap() = 99 };
+    assert_eq!(data[0], 99);
+}
+
+#[test]
+fn raw_view_get_ptr_checks_bounds_in_3d() {
+    // `get_ptr`/`get_mut_ptr` are inherited from `ArrayBase` for any `RawData`, so raw
+    // views already get checked, stride-aware pointer lookup without duplicating the
+    // offset arithmetic that `split_at` does for single-axis splits.
+    let a = Array::from_shape_fn((2, 3, 4), |(i, j, k)| i * 100 + j * 10 + k);
+    let raw_view = a.raw_view();
+
+    let p = raw_view.get_ptr((1, 2, 3)).unwrap();
+    assert_eq!(unsafe { *p }, 123);
+
+    assert!(raw_view.get_ptr((2, 0, 0)).is_none());
+    assert!(raw_view.get_ptr((0, 3, 0)).is_none());
+    assert!(raw_view.get_ptr((0, 0, 4)).is_none());
+
+    let mut b = a.clone();
+    let mut raw_view_mut = b.raw_view_mut();
+    let p = raw_view_mut.get_mut_ptr((1, 2, 3)).unwrap();
+    unsafe { *p = 999 };
+    assert_eq!(b[(1, 2, 3)], 999);
+    assert!(raw_view_mut.get_mut_ptr((2, 0, 0)).is_none());
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "serde")]
+
+use ndarray::{array, NdArrayMsg};
+
+#[test]
+fn nd_array_msg_round_trips_2x3() {
+    let arr = array![[1, 2, 3], [4, 5, 6]].into_dyn();
+
+    let msg = NdArrayMsg(arr.clone());
+    let encoded = serde_json::to_string(&msg).unwrap();
+
+    let decoded: NdArrayMsg<i32> = serde_json::from_str(&encoded).unwrap();
+    assert_eq!(decoded.0, arr);
+}
+
+#[test]
+fn nd_array_msg_rejects_mismatched_length() {
+    // shape calls for 6 elements, but only 5 are provided.
+    let bad = r#"{"shape":[2,3],"data":[1,2,3,4,5]}"#;
+
+    let res: Result<NdArrayMsg<i32>, _> = serde_json::from_str(bad);
+    assert!(res.is_err());
+}
@@ -48,6 +48,23 @@ mod sealed {
         /// Determines the value of `Serializer::is_human_readable` and
         /// `Deserializer::is_human_readable`.
         fn is_human_readable() -> bool;
+
+        /// Whether struct fields must be buffered and written in lexicographically sorted
+        /// key order rather than in declaration order.
+        ///
+        /// This only affects configurations that also write structs as maps, since
+        /// declaration order is meaningless for tuple-encoded structs.
+        fn sorts_struct_fields() -> bool {
+            false
+        }
+
+        /// Whether struct field names should be deduplicated via the string interning
+        /// extension instead of being written out in full on every occurrence.
+        ///
+        /// See [`StringInterningConfig`](super::StringInterningConfig) for the wire format.
+        fn interns_struct_fields() -> bool {
+            false
+        }
     }
 }
 
@@ -163,6 +180,220 @@ where
     }
 }
 
+/// Config wrapper that overrides struct serialization by packing as a map with field names,
+/// sorted lexicographically by field name instead of in declaration order.
+///
+/// Unlike `HashMap`s, whose key order MessagePack encoders may already shuffle, struct field
+/// order is statically known, so this exists purely to give callers a canonical, order-independent
+/// encoding when they need one (e.g. for hashing or diffing). Fields are buffered until all of
+/// them have been visited, then flushed in sorted order.
+#[derive(Copy, Clone, Debug)]
+pub struct StructMapSortedConfig<C>(C);
+
+impl<C> StructMapSortedConfig<C> {
+    /// Creates a `StructMapSortedConfig` inheriting unchanged configuration options from the given configuration.
+    #[inline]
+    pub fn new(inner: C) -> Self {
+        StructMapSortedConfig(inner)
+    }
+}
+
+impl<C> sealed::SerializerConfig for StructMapSortedConfig<C>
+where
+    C: sealed::SerializerConfig,
+{
+    fn write_struct_len<S>(ser: &mut S, len: usize) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        encode::write_map_len(ser.get_mut(), len as u32)?;
+
+        Ok(())
+    }
+
+    fn write_struct_field<S, T>(ser: &mut S, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+        T: ?Sized + Serialize,
+    {
+        encode::write_str(ser.get_mut(), key)?;
+        value.serialize(ser)
+    }
+
+    #[inline]
+    fn write_variant_ident<S>(
+        ser: &mut S,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        C::write_variant_ident(ser, variant_index, variant)
+    }
+
+    #[inline(always)]
+    fn is_human_readable() -> bool {
+        C::is_human_readable()
+    }
+
+    #[inline(always)]
+    fn sorts_struct_fields() -> bool {
+        true
+    }
+}
+
+/// Config wrapper that overrides struct serialization by packing as a map with field names,
+/// deduplicating repeated field names via a non-standard string interning extension.
+///
+/// The first time a given field name is written in a serialization session, it is encoded as an
+/// ordinary MessagePack string, same as [`StructMapConfig`]. Every subsequent occurrence of that
+/// same name is instead written as a 1-byte [application-specific ext type][ext] (tag
+/// [`STRING_INTERN_EXT_TYPE`](crate::encode::STRING_INTERN_EXT_TYPE)) carrying the index it was
+/// first assigned. This is a significant space saving when serializing many records that share a
+/// schema, e.g. a `Vec` of identical-shaped structs, at the cost of producing MessagePack that
+/// only a decoder aware of this extension (such as this crate's own, with the matching option
+/// enabled) can read back.
+///
+/// Since the table lives on the `Serializer` for the duration of a session, this only
+/// deduplicates field names *within* a single top-level `serialize` call; it is not a
+/// document-wide, upfront dictionary.
+///
+/// [ext]: https://github.com/msgpack/msgpack/blob/master/spec.md#ext-format-family
+#[derive(Copy, Clone, Debug)]
+pub struct StringInterningConfig<C>(C);
+
+impl<C> StringInterningConfig<C> {
+    /// Creates a `StringInterningConfig` inheriting unchanged configuration options from the given configuration.
+    #[inline]
+    pub fn new(inner: C) -> Self {
+        StringInterningConfig(inner)
+    }
+}
+
+impl<C> sealed::SerializerConfig for StringInterningConfig<C>
+where
+    C: sealed::SerializerConfig,
+{
+    fn write_struct_len<S>(ser: &mut S, len: usize) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        encode::write_map_len(ser.get_mut(), len as u32)?;
+
+        Ok(())
+    }
+
+    fn write_struct_field<S, T>(ser: &mut S, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+        T: ?Sized + Serialize,
+    {
+        // The actual interning happens in `Compound::serialize_field`, which has access to the
+        // `Serializer`'s interning table; by the time a field reaches here (e.g. as part of a
+        // buffered, unknown-length map) it is written out in full.
+        encode::write_str(ser.get_mut(), key)?;
+        value.serialize(ser)
+    }
+
+    #[inline]
+    fn write_variant_ident<S>(
+        ser: &mut S,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        C::write_variant_ident(ser, variant_index, variant)
+    }
+
+    #[inline(always)]
+    fn is_human_readable() -> bool {
+        C::is_human_readable()
+    }
+
+    #[inline(always)]
+    fn interns_struct_fields() -> bool {
+        true
+    }
+}
+
+/// Config wrapper that overrides externally-tagged enum variant serialization to write the
+/// variant's declaration-order index instead of its name.
+///
+/// This roughly halves the per-variant overhead for enums with long variant names, at the cost
+/// of the encoding no longer being self-describing: a `Deserializer` decodes a variant index
+/// back to its name generically (the same way `#[derive(Deserialize)]` already resolves struct
+/// field names given as integer keys), so no matching `Deserializer` option is needed, but the
+/// two ends of a connection must still agree on variant declaration order.
+#[derive(Copy, Clone, Debug)]
+pub struct IntegerVariantConfig<C>(C);
+
+impl<C> IntegerVariantConfig<C> {
+    /// Creates an `IntegerVariantConfig` inheriting unchanged configuration options from the given configuration.
+    #[inline]
+    pub fn new(inner: C) -> Self {
+        IntegerVariantConfig(inner)
+    }
+}
+
+impl<C> sealed::SerializerConfig for IntegerVariantConfig<C>
+where
+    C: sealed::SerializerConfig,
+{
+    fn write_struct_len<S>(ser: &mut S, len: usize) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        C::write_struct_len(ser, len)
+    }
+
+    fn write_struct_field<S, T>(ser: &mut S, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+        T: ?Sized + Serialize,
+    {
+        C::write_struct_field(ser, key, value)
+    }
+
+    #[inline]
+    fn write_variant_ident<S>(
+        ser: &mut S,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error>
+    where
+        S: UnderlyingWrite,
+        for<'a> &'a mut S: Serializer<Ok = (), Error = Error>,
+    {
+        ser.serialize_u32(variant_index)
+    }
+
+    #[inline(always)]
+    fn is_human_readable() -> bool {
+        C::is_human_readable()
+    }
+
+    #[inline(always)]
+    fn sorts_struct_fields() -> bool {
+        C::sorts_struct_fields()
+    }
+
+    #[inline(always)]
+    fn interns_struct_fields() -> bool {
+        C::interns_struct_fields()
+    }
+}
+
 /// Config wrapper that overrides struct serlization by packing as a tuple without field
 /// names.
 #[derive(Copy, Clone, Debug)]
@@ -0,0 +1,146 @@
+//! Human-readable annotation of a MessagePack byte stream, for tracking down protocol mismatches.
+
+use rmp::Marker;
+
+use crate::decode::Error;
+
+/// Walks `bytes` as a sequence of MessagePack values and returns one line per marker, each
+/// showing that marker's header bytes in hex followed by a short label, e.g.:
+///
+/// ```text
+/// 90         fixarray(0)
+/// a3 66 6f 6f str3("foo")
+/// ```
+///
+/// Every byte of the value is shown in the hex column: the marker itself, any length prefix or
+/// ext type tag, and any `str`/`bin`/`ext` payload bytes. Array and map elements are not indented or grouped -- they
+/// simply appear as the following lines, in wire order.
+///
+/// This does not deserialize into any particular Rust type, so it can annotate a stream even
+/// when the type it was meant for is unknown or the two disagree, which is the point: it is a
+/// diagnostic aid for protocol mismatches, not a general-purpose parser.
+pub fn annotate(bytes: &[u8]) -> Result<String, Error> {
+    let mut out = String::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let start = pos;
+        let marker = Marker::from_u8(bytes[pos]);
+        pos += 1;
+
+        let label = match marker {
+            Marker::FixPos(v) => format!("fixpos({})", v),
+            Marker::FixNeg(v) => format!("fixneg({})", v),
+            Marker::Null => "nil".to_string(),
+            Marker::True => "true".to_string(),
+            Marker::False => "false".to_string(),
+            Marker::U8 => format!("u8({})", read_scalar(bytes, &mut pos, 1)?),
+            Marker::U16 => format!("u16({})", read_scalar(bytes, &mut pos, 2)?),
+            Marker::U32 => format!("u32({})", read_scalar(bytes, &mut pos, 4)?),
+            Marker::U64 => format!("u64({})", read_scalar(bytes, &mut pos, 8)?),
+            Marker::I8 => format!("i8({})", read_scalar(bytes, &mut pos, 1)?),
+            Marker::I16 => format!("i16({})", read_scalar(bytes, &mut pos, 2)?),
+            Marker::I32 => format!("i32({})", read_scalar(bytes, &mut pos, 4)?),
+            Marker::I64 => format!("i64({})", read_scalar(bytes, &mut pos, 8)?),
+            Marker::F32 => format!("f32({})", read_scalar(bytes, &mut pos, 4)?),
+            Marker::F64 => format!("f64({})", read_scalar(bytes, &mut pos, 8)?),
+            Marker::FixStr(len) => label_str(bytes, &mut pos, len as u32)?,
+            Marker::Str8 => {
+                let len = read_len(bytes, &mut pos, 1)?;
+                label_str(bytes, &mut pos, len)?
+            }
+            Marker::Str16 => {
+                let len = read_len(bytes, &mut pos, 2)?;
+                label_str(bytes, &mut pos, len)?
+            }
+            Marker::Str32 => {
+                let len = read_len(bytes, &mut pos, 4)?;
+                label_str(bytes, &mut pos, len)?
+            }
+            Marker::Bin8 => {
+                let len = read_len(bytes, &mut pos, 1)?;
+                skip(bytes, &mut pos, len)?;
+                format!("bin8({})", len)
+            }
+            Marker::Bin16 => {
+                let len = read_len(bytes, &mut pos, 2)?;
+                skip(bytes, &mut pos, len)?;
+                format!("bin16({})", len)
+            }
+            Marker::Bin32 => {
+                let len = read_len(bytes, &mut pos, 4)?;
+                skip(bytes, &mut pos, len)?;
+                format!("bin32({})", len)
+            }
+            Marker::FixArray(len) => format!("fixarray({})", len),
+            Marker::Array16 => format!("array16({})", read_len(bytes, &mut pos, 2)?),
+            Marker::Array32 => format!("array32({})", read_len(bytes, &mut pos, 4)?),
+            Marker::FixMap(len) => format!("fixmap({})", len),
+            Marker::Map16 => format!("map16({})", read_len(bytes, &mut pos, 2)?),
+            Marker::Map32 => format!("map32({})", read_len(bytes, &mut pos, 4)?),
+            Marker::FixExt1 => label_ext(bytes, &mut pos, 1, "fixext1")?,
+            Marker::FixExt2 => label_ext(bytes, &mut pos, 2, "fixext2")?,
+            Marker::FixExt4 => label_ext(bytes, &mut pos, 4, "fixext4")?,
+            Marker::FixExt8 => label_ext(bytes, &mut pos, 8, "fixext8")?,
+            Marker::FixExt16 => label_ext(bytes, &mut pos, 16, "fixext16")?,
+            Marker::Ext8 => {
+                let len = read_len(bytes, &mut pos, 1)?;
+                label_ext(bytes, &mut pos, len, "ext8")?
+            }
+            Marker::Ext16 => {
+                let len = read_len(bytes, &mut pos, 2)?;
+                label_ext(bytes, &mut pos, len, "ext16")?
+            }
+            Marker::Ext32 => {
+                let len = read_len(bytes, &mut pos, 4)?;
+                label_ext(bytes, &mut pos, len, "ext32")?
+            }
+            Marker::Reserved => "reserved".to_string(),
+        };
+
+        let hex: Vec<String> = bytes[start..pos].iter().map(|b| format!("{:02x}", b)).collect();
+        out.push_str(&format!("{:<10} {}\n", hex.join(" "), label));
+    }
+
+    Ok(out)
+}
+
+fn take<'a>(bytes: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8], Error> {
+    let end = pos.checked_add(len).filter(|&end| end <= bytes.len());
+    match end {
+        Some(end) => {
+            let slice = &bytes[*pos..end];
+            *pos = end;
+            Ok(slice)
+        }
+        None => Err(Error::Uncategorized("unexpected end of buffer while annotating".to_string())),
+    }
+}
+
+fn read_len(bytes: &[u8], pos: &mut usize, width: usize) -> Result<u32, Error> {
+    let buf = take(bytes, pos, width)?;
+    Ok(buf.iter().fold(0u32, |acc, &b| (acc << 8) | u32::from(b)))
+}
+
+fn read_scalar(bytes: &[u8], pos: &mut usize, width: usize) -> Result<u64, Error> {
+    let buf = take(bytes, pos, width)?;
+    Ok(buf.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b)))
+}
+
+fn skip(bytes: &[u8], pos: &mut usize, len: u32) -> Result<(), Error> {
+    take(bytes, pos, len as usize).map(|_| ())
+}
+
+fn label_str(bytes: &[u8], pos: &mut usize, len: u32) -> Result<String, Error> {
+    let buf = take(bytes, pos, len as usize)?;
+    match std::str::from_utf8(buf) {
+        Ok(s) => Ok(format!("str{}({:?})", len, s)),
+        Err(_) => Ok(format!("str{}(<invalid utf8>)", len)),
+    }
+}
+
+fn label_ext(bytes: &[u8], pos: &mut usize, len: u32, name: &str) -> Result<String, Error> {
+    let tag = take(bytes, pos, 1)?[0] as i8;
+    skip(bytes, pos, len)?;
+    Ok(format!("{}(type={}, len={})", name, tag, len))
+}
@@ -1,6 +1,7 @@
 //! Generic MessagePack deserialization.
 
-use std::convert::TryInto;
+use std::collections::HashSet;
+use std::convert::{TryFrom, TryInto};
 use std::error;
 use std::fmt::{self, Display, Formatter};
 use std::io::{self, Cursor, ErrorKind, Read};
@@ -10,13 +11,14 @@ use std::str::{self, Utf8Error};
 use byteorder::{self, ReadBytesExt};
 
 use serde;
-use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, Unexpected, Visitor};
+use serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, IgnoredAny, Unexpected, Visitor};
 
 use rmp;
 use rmp::decode::{self, RmpRead, DecodeStringError, MarkerReadError, NumValueReadError, ValueReadError};
 use rmp::Marker;
 
 use crate::config::{BinaryConfig, DefaultConfig, HumanReadableConfig, SerializerConfig};
+use crate::encode::{DURATION_EXT_TYPE, STRING_INTERN_EXT_TYPE};
 use crate::MSGPACK_EXT_STRUCT_NAME;
 
 /// Enum representing errors that can occur while decoding MessagePack data.
@@ -34,6 +36,12 @@ pub enum Error {
     OutOfRange,
     /// A decoded array did not have the enclosed expected length.
     LengthMismatch(u32),
+    /// A single `str`/`bin`/ext length prefix declared more bytes than remained of the budget
+    /// set by [`Deserializer::with_bytes_limit`].
+    LimitExceeded,
+    /// A declared `array`/`map` length exceeded the cap set by
+    /// [`Deserializer::with_max_array_len`].
+    ArrayLenExceeded,
     /// An otherwise uncategorized error occurred. See the enclosed `String` for
     /// details.
     Uncategorized(String),
@@ -44,6 +52,13 @@ pub enum Error {
     Utf8Error(Utf8Error),
     /// The depth limit was exceeded.
     DepthLimitExceeded,
+    /// The input ended before a value finished decoding, distinct from other I/O failures.
+    /// `needed` is a best-effort estimate of how many more bytes were required at the point the
+    /// read failed; it is not a guarantee of how many bytes remain to complete the whole value.
+    Truncated {
+        /// Best-effort estimate of how many more bytes were required.
+        needed: usize,
+    },
 }
 
 macro_rules! depth_count(
@@ -60,6 +75,34 @@ macro_rules! depth_count(
     }
 );
 
+/// Defines a `deserialize_$method` override that, when [`Deserializer::with_float_to_int`] is
+/// enabled and the next marker is a `float`, consumes it and hands the visitor an integer
+/// converted from it, instead of falling through to `deserialize_any`'s marker-typed dispatch.
+macro_rules! deserialize_int_or_float(
+    ($method:ident, $visit:ident) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where V: Visitor<'de>
+        {
+            if self.float_to_int {
+                match self.peek_or_read_marker()? {
+                    Marker::F32 => {
+                        self.marker = None;
+                        let v = self.rd.read_data_f32()? as f64;
+                        return visitor.$visit(float_as_int(v)?);
+                    }
+                    Marker::F64 => {
+                        self.marker = None;
+                        let v = self.rd.read_data_f64()?;
+                        return visitor.$visit(float_as_int(v)?);
+                    }
+                    _ => {}
+                }
+            }
+            self.deserialize_any(visitor)
+        }
+    }
+);
+
 impl error::Error for Error {
     #[cold]
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
@@ -68,11 +111,14 @@ impl error::Error for Error {
             Error::InvalidMarkerRead(ref err) => Some(err),
             Error::InvalidDataRead(ref err) => Some(err),
             Error::LengthMismatch(..) => None,
+            Error::LimitExceeded => None,
+            Error::ArrayLenExceeded => None,
             Error::OutOfRange => None,
             Error::Uncategorized(..) => None,
             Error::Syntax(..) => None,
             Error::Utf8Error(ref err) => Some(err),
             Error::DepthLimitExceeded => None,
+            Error::Truncated { .. } => None,
         }
     }
 }
@@ -99,19 +145,36 @@ impl Display for Error {
                 "array had incorrect length, expected {}",
                 expected_length
             ),
+            Error::LimitExceeded => fmt.write_str("length prefix exceeded the configured bytes limit"),
+            Error::ArrayLenExceeded => fmt.write_str("array or map length exceeded the configured element cap"),
             Error::Uncategorized(ref msg) => write!(fmt, "uncategorized error: {}", msg),
             Error::Syntax(ref msg) => fmt.write_str(msg),
             Error::Utf8Error(ref err) => write!(fmt, "string found to be invalid utf8: {}", err),
             Error::DepthLimitExceeded => fmt.write_str("depth limit exceeded"),
+            Error::Truncated { needed } => {
+                write!(fmt, "input ended before the value completed, needed ~{} more byte(s)", needed)
+            }
         }
     }
 }
 
+/// Turns an I/O error from a marker/data read into an [`Error`], recognizing EOF as
+/// [`Error::Truncated`] rather than folding it into the generic `fallback` variant. `needed` is
+/// the number of bytes that specific read was attempting to consume.
+#[cold]
+fn io_err_to_error(err: io::Error, needed: usize, fallback: fn(io::Error) -> Error) -> Error {
+    if err.kind() == ErrorKind::UnexpectedEof {
+        Error::Truncated { needed }
+    } else {
+        fallback(err)
+    }
+}
+
 impl From<MarkerReadError> for Error {
     #[cold]
     fn from(err: MarkerReadError) -> Error {
         match err {
-            MarkerReadError(err) => Error::InvalidMarkerRead(err),
+            MarkerReadError(err) => io_err_to_error(err, 1, Error::InvalidMarkerRead),
         }
     }
 }
@@ -128,8 +191,8 @@ impl From<ValueReadError> for Error {
     fn from(err: ValueReadError) -> Error {
         match err {
             ValueReadError::TypeMismatch(marker) => Error::TypeMismatch(marker),
-            ValueReadError::InvalidMarkerRead(err) => Error::InvalidMarkerRead(err),
-            ValueReadError::InvalidDataRead(err) => Error::InvalidDataRead(err),
+            ValueReadError::InvalidMarkerRead(err) => io_err_to_error(err, 1, Error::InvalidMarkerRead),
+            ValueReadError::InvalidDataRead(err) => io_err_to_error(err, 1, Error::InvalidDataRead),
         }
     }
 }
@@ -139,8 +202,8 @@ impl From<NumValueReadError> for Error {
     fn from(err: NumValueReadError) -> Error {
         match err {
             NumValueReadError::TypeMismatch(marker) => Error::TypeMismatch(marker),
-            NumValueReadError::InvalidMarkerRead(err) => Error::InvalidMarkerRead(err),
-            NumValueReadError::InvalidDataRead(err) => Error::InvalidDataRead(err),
+            NumValueReadError::InvalidMarkerRead(err) => io_err_to_error(err, 1, Error::InvalidMarkerRead),
+            NumValueReadError::InvalidDataRead(err) => io_err_to_error(err, 1, Error::InvalidDataRead),
             NumValueReadError::OutOfRange => Error::OutOfRange,
         }
     }
@@ -150,8 +213,8 @@ impl<'a> From<DecodeStringError<'a>> for Error {
     #[cold]
     fn from(err: DecodeStringError<'_>) -> Error {
         match err {
-            DecodeStringError::InvalidMarkerRead(err) => Error::InvalidMarkerRead(err),
-            DecodeStringError::InvalidDataRead(err) => Error::InvalidDataRead(err),
+            DecodeStringError::InvalidMarkerRead(err) => io_err_to_error(err, 1, Error::InvalidMarkerRead),
+            DecodeStringError::InvalidDataRead(err) => io_err_to_error(err, 1, Error::InvalidDataRead),
             DecodeStringError::TypeMismatch(marker) => Error::TypeMismatch(marker),
             DecodeStringError::BufferSizeTooSmall(..) => Error::Uncategorized("BufferSizeTooSmall".to_string()),
             DecodeStringError::InvalidUtf8(..) => Error::Uncategorized("InvalidUtf8".to_string()),
@@ -166,6 +229,32 @@ impl From<TryFromIntError> for Error {
     }
 }
 
+/// How [`Deserializer`] handles a map or struct-as-map payload that contains the same string key
+/// more than once. See [`Deserializer::with_duplicate_key_policy`].
+///
+/// Note this only governs which occurrence(s) of a key `Deserializer`'s own `MapAccess` hands to
+/// the target's `Visitor` — it doesn't change how a `#[derive(Deserialize)]` struct reacts once a
+/// key reaches it. A derived struct's generated `Visitor` always errors on seeing the same known
+/// field a second time, independent of this setting, so only [`DuplicateKeyPolicy::FirstWins`]
+/// (which hides every occurrence but the first from the `Visitor` entirely) actually lets a
+/// struct with duplicate keys decode successfully; [`DuplicateKeyPolicy::LastWins`] and
+/// [`DuplicateKeyPolicy::Error`] both still surface every occurrence, so a derived struct still
+/// errors under either of them. A `HashMap`/`BTreeMap` target has no such built-in check, so all
+/// three policies behave as their names suggest there.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// Every occurrence of a key is handed to the target's `Visitor` as normal; a target that
+    /// naturally overwrites on insert (like `HashMap`) ends up keeping the last one. This is the
+    /// default, matching this crate's behavior before this policy existed.
+    #[default]
+    LastWins,
+    /// Only the first occurrence of a key is handed to the target's `Visitor`; later duplicates
+    /// are still decoded, to advance the reader past them, but their value is discarded.
+    FirstWins,
+    /// Fails with [`Error::Uncategorized`] as soon as a key repeats.
+    Error,
+}
+
 /// A Deserializer that reads bytes from a buffer.
 ///
 /// # Note
@@ -178,6 +267,74 @@ pub struct Deserializer<R, C = DefaultConfig> {
     config: C,
     marker: Option<Marker>,
     depth: usize,
+    /// Struct field names seen so far, in the order they were first written, for use by
+    /// [`Self::with_string_interning`]. `None` unless that option is enabled.
+    intern: Option<Vec<String>>,
+    /// Whether externally-tagged enum variants with data may also be read from a 2-element
+    /// array `[variant, data]`, not just the standard 1-entry map `{variant: data}`. See
+    /// [`Self::with_lenient_enums`].
+    lenient_enums: bool,
+    /// How a map or struct-as-map payload with two string keys of the same name is handled. See
+    /// [`Self::with_duplicate_key_policy`].
+    duplicate_key_policy: DuplicateKeyPolicy,
+    /// Remaining budget for `str`/`bin`/ext length prefixes, decremented as they are read.
+    /// `None` unless [`Self::with_bytes_limit`] was used, in which case it starts at the
+    /// configured limit. `array`/`map` length prefixes declare an element *count*, not a byte
+    /// length, so they're bounded by [`Self::with_max_array_len`] instead.
+    bytes_budget: Option<usize>,
+    /// Maximum number of elements a single `array`/`map` length prefix may declare. `None`
+    /// unless [`Self::with_max_array_len`] was used.
+    max_array_len: Option<usize>,
+    /// Whether a `nil` marker deserializes to an empty sequence/map when a seq/map is expected,
+    /// rather than erroring. See [`Self::with_nil_as_empty_collection`].
+    nil_as_empty_collection: bool,
+    /// Whether a `float` marker is accepted in place of an integer field, provided it has no
+    /// fractional part and fits in the target type. See [`Self::with_float_to_int`].
+    float_to_int: bool,
+    /// Whether tuples and tuple-structs may also be read from an integer-keyed map `{0: v0,
+    /// 1: v1, ...}`, in addition to the standard array encoding. See
+    /// [`Self::with_tuple_as_map`].
+    tuple_as_map: bool,
+    /// Whether a struct-as-tuple array shorter than the struct's field count is accepted, with
+    /// missing trailing fields defaulted rather than erroring. See
+    /// [`Self::with_trailing_optional`].
+    trailing_optional: bool,
+    /// Whether decoding an externally-tagged enum whose wire variant name is not among the
+    /// target enum's known `variants` substitutes the literal name `"other"` instead of
+    /// erroring. See [`Self::with_unknown_variant_fallback`].
+    unknown_variant_fallback: bool,
+    /// Whether an externally-tagged enum variant with data may also be read from the
+    /// [`Self::with_lenient_enums`] 2-element array form even without that option being set.
+    /// See [`Self::with_enum_repr_autodetect`].
+    enum_repr_autodetect: bool,
+    /// Handlers registered via [`Self::with_ext_handler`], tried in registration order against
+    /// an ext type's tag whenever one is encountered without a target type telling us how to
+    /// decode it (i.e. via [`Self::deserialize_any`][de::Deserializer::deserialize_any]).
+    ext_handlers: Vec<(i8, ExtHandler)>,
+    /// Whether `std::time::Duration` is read back from the compact ext encoding written by
+    /// [`crate::encode::Serializer::with_ext_durations`], instead of its default 2-field struct
+    /// representation. See [`Self::with_ext_durations`].
+    ext_durations: bool,
+    /// Whether a struct-as-map payload whose keys don't match any of the target struct's field
+    /// names falls back to matching fields positionally, by entry order. See
+    /// [`Self::with_lenient_map_to_struct_ordering`].
+    lenient_map_to_struct_ordering: bool,
+    /// Whether decoding a `float32` into an `f64` field (or a `float64` into an `f32` field)
+    /// errors instead of silently widening/narrowing. See [`Self::with_strict_float_width`].
+    strict_float_width: bool,
+}
+
+/// A handler registered via [`Deserializer::with_ext_handler`]. Wraps a boxed closure with a
+/// trivial [`Debug`](fmt::Debug) impl, since closures themselves don't implement it, so that
+/// `#[derive(Debug)]` on [`Deserializer`] keeps working unconditionally.
+type ExtHandlerFn = dyn Fn(&[u8]) -> Result<u64, Error>;
+
+struct ExtHandler(Box<ExtHandlerFn>);
+
+impl fmt::Debug for ExtHandler {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("ExtHandler(..)")
+    }
 }
 
 impl<R: Read, C> Deserializer<R, C> {
@@ -209,6 +366,21 @@ impl<R: Read> Deserializer<ReadReader<R>, DefaultConfig> {
             // Cached marker in case of deserializing optional values.
             marker: None,
             depth: 1024,
+            intern: None,
+            lenient_enums: false,
+            duplicate_key_policy: DuplicateKeyPolicy::LastWins,
+            bytes_budget: None,
+            max_array_len: None,
+            nil_as_empty_collection: false,
+            float_to_int: false,
+            tuple_as_map: false,
+            trailing_optional: false,
+            unknown_variant_fallback: false,
+            enum_repr_autodetect: false,
+            ext_handlers: Vec::new(),
+            ext_durations: false,
+            lenient_map_to_struct_ordering: false,
+            strict_float_width: false,
         }
     }
 }
@@ -241,12 +413,27 @@ impl<R: Read, C: SerializerConfig> Deserializer<R, C> {
     /// versions of `rmp-serde`.
     #[inline]
     pub fn with_human_readable(self) -> Deserializer<R, HumanReadableConfig<C>> {
-        let Deserializer { rd, config, marker, depth } = self;
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width } = self;
         Deserializer {
             rd,
             config: HumanReadableConfig::new(config),
             marker,
             depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
         }
     }
 
@@ -257,14 +444,593 @@ impl<R: Read, C: SerializerConfig> Deserializer<R, C> {
     /// representation.
     #[inline]
     pub fn with_binary(self) -> Deserializer<R, BinaryConfig<C>> {
-        let Deserializer { rd, config, marker, depth } = self;
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width } = self;
         Deserializer {
             rd,
             config: BinaryConfig::new(config),
             marker,
             depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that understands the non-standard
+    /// struct field name interning extension produced by
+    /// [`Serializer::with_string_interning`](crate::encode::Serializer::with_string_interning):
+    /// a field name ext-referencing a previous index is resolved back to the name it stands for.
+    ///
+    /// Field names that are not interned (plain strings) are still accepted as usual, so this is
+    /// safe to enable even if not every value read through this `Deserializer` used interning.
+    #[inline]
+    pub fn with_string_interning(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern: Some(Vec::new()),
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that also accepts externally-tagged
+    /// enum variants with data encoded as a 2-element array `[variant, data]`, in addition to
+    /// the standard 1-entry map `{variant: data}` this crate writes.
+    ///
+    /// This aids interop with other MessagePack producers (e.g. some Python libraries) that
+    /// favor the more compact array form. Both encodings are accepted for the lifetime of this
+    /// `Deserializer` once enabled; there is no matching `Serializer` option since this crate
+    /// always writes the map form.
+    #[inline]
+    pub fn with_lenient_enums(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums: true,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that errors if a struct-as-map payload
+    /// contains the same string key twice, rather than silently letting the later occurrence win.
+    ///
+    /// This only guards against duplicate string keys; it does not affect maps keyed by other
+    /// types, such as the integer field keys `#[derive(Deserialize)]` also accepts positionally.
+    ///
+    /// Equivalent to `.with_duplicate_key_policy(DuplicateKeyPolicy::Error)`.
+    #[inline]
+    pub fn with_reject_duplicate_keys(self) -> Deserializer<R, C> {
+        self.with_duplicate_key_policy(DuplicateKeyPolicy::Error)
+    }
+
+    /// Consumes this deserializer and returns a new one that reads a map or struct-as-map
+    /// payload's repeated string keys according to `policy`, instead of the default
+    /// [`DuplicateKeyPolicy::LastWins`].
+    ///
+    /// This only governs duplicate string keys; it does not affect maps keyed by other types,
+    /// such as the integer field keys `#[derive(Deserialize)]` also accepts positionally.
+    #[inline]
+    pub fn with_duplicate_key_policy(self, policy: DuplicateKeyPolicy) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy: policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that errors with
+    /// [`Error::LimitExceeded`] if any single `str`/`bin`/ext length prefix declares more than
+    /// `limit` bytes remaining in the budget, which is shared and decremented across the whole
+    /// deserialization.
+    ///
+    /// This guards against a hostile payload declaring an enormous length in order to force a
+    /// correspondingly enormous allocation from just a few bytes of input: since a single
+    /// length prefix can never exceed the remaining budget, no allocation can either. `array`/
+    /// `map` length prefixes declare an element *count* rather than a byte length, so this
+    /// doesn't bound them; use [`Self::with_max_array_len`] for that instead.
+    #[inline]
+    pub fn with_bytes_limit(self, limit: usize) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget: Some(limit),
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that errors with
+    /// [`Error::ArrayLenExceeded`] if any single `array`/`map` length prefix declares more than
+    /// `max_len` elements.
+    ///
+    /// Unlike [`Self::with_bytes_limit`], this caps the element *count* rather than the byte
+    /// size of the length prefix, which guards against quadratic work from many small elements
+    /// (e.g. a huge array of fixints) that a bytes budget alone would not catch.
+    #[inline]
+    pub fn with_max_array_len(self, max_len: usize) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len: Some(max_len),
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that treats a `nil` marker as an empty
+    /// sequence or map when a seq/map is expected, instead of erroring.
+    ///
+    /// This accommodates producers that emit `nil` for an omitted collection rather than an
+    /// empty array/map. Strict behavior (erroring on `nil` where a seq/map is expected) remains
+    /// the default.
+    #[inline]
+    pub fn with_nil_as_empty_collection(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection: true,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that accepts a `float` in place of an
+    /// integer field, provided the float has no fractional part and fits in the target type.
+    ///
+    /// This accommodates producers (e.g. JavaScript's `msgpack-lite`) that don't distinguish
+    /// integers from floats and may emit a whole number like `5.0` where a strict schema expects
+    /// an integer. A float with a nonzero fractional part, or one that doesn't fit in the target
+    /// integer type, is still rejected with [`Error::OutOfRange`].
+    #[inline]
+    pub fn with_float_to_int(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int: true,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that errors if the width of an encoded
+    /// `float` doesn't match the target Rust type: decoding a `float32` into an `f64` field, or a
+    /// `float64` into an `f32` field, fails with [`Error::TypeMismatch`] instead of
+    /// silently widening or narrowing.
+    ///
+    /// The default is permissive, matching serde's usual behavior for numeric types: a `float32`
+    /// read into an `f64` field widens exactly (every `f32` value is exactly representable as
+    /// `f64`), and a `float64` read into an `f32` field narrows via `as` conversion. Enable this
+    /// when bit-exactness with the wire encoding matters more than that convenience.
+    #[inline]
+    pub fn with_strict_float_width(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width: true,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that also accepts a tuple or
+    /// tuple-struct encoded as an integer-keyed map `{0: v0, 1: v1, ...}`, in addition to the
+    /// standard array encoding.
+    ///
+    /// Pair with [`crate::encode::Serializer::with_tuple_as_map`] on the writing end. The
+    /// standard array encoding is still accepted once this is enabled, so this is safe to turn
+    /// on even when reading a mix of old and new payloads.
+    #[inline]
+    pub fn with_tuple_as_map(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map: true,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that accepts a struct-as-tuple array
+    /// shorter than the struct's field count, defaulting the missing trailing fields instead of
+    /// erroring.
+    ///
+    /// A trailing field is only defaulted if the field type itself tolerates it: an `Option<T>`
+    /// field is set to `None`, and a field annotated `#[serde(default)]` falls back to its
+    /// configured default. Any other missing field still errors, as does a shorter array that
+    /// omits a *non-trailing* field. Pair with a `Serializer` that may omit trailing fields when
+    /// they're at their default, to save space on the wire.
+    #[inline]
+    pub fn with_trailing_optional(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional: true,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that maps an externally-tagged enum's
+    /// unrecognized wire variant name to the literal name `"other"`, instead of erroring.
+    ///
+    /// This is meant to be paired with a target enum that declares a fallback variant serialized
+    /// as `"other"` (whether the Rust variant is literally named `other` or renamed to it via
+    /// `#[serde(rename = "other")]`), to stay forward-compatible with variants added to the wire
+    /// format later. Note this is unrelated to serde's own `#[serde(other)]` attribute, which
+    /// already accepts any unrecognized name unconditionally and so needs no help from this
+    /// flag; this flag instead lets a plain `"other"`-named variant serve the same purpose only
+    /// when explicitly opted into.
+    #[inline]
+    pub fn with_unknown_variant_fallback(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, enum_repr_autodetect, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback: true,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that, like [`Self::with_lenient_enums`],
+    /// also accepts an externally-tagged enum variant's data from the 2-element array form
+    /// `[variant, data]`, not just the standard 1-entry map `{variant: data}`.
+    ///
+    /// This is really just a more discoverable name for the same capability as
+    /// [`Self::with_lenient_enums`] (either one enables it): a variant's identifier is already
+    /// accepted whether it arrives as a bare MessagePack string (`"string variant"`, used by the
+    /// default [`Serializer`](crate::encode::Serializer) config) or a bare integer
+    /// (`"integer variant"`, used by
+    /// [`Serializer::with_integer_variants`](crate::encode::Serializer::with_integer_variants)),
+    /// and a variant carrying data is already accepted as a 1-entry map regardless of which of
+    /// those two idents it's keyed by — none of that needs opting into. The only wire shape this
+    /// method (or `with_lenient_enums`) adds acceptance for is the 2-element array alternative
+    /// to that map.
+    ///
+    /// # Ambiguities
+    ///
+    /// This inspects only the *next* MessagePack marker, not the target enum's shape, so it
+    /// cannot help with representations that never call into [`Deserializer::deserialize_enum`]
+    /// at all: serde's `#[serde(untagged)]` (which buffers the whole value via `Content` and
+    /// tries each variant's `Deserialize` impl in turn) and `#[serde(tag = "...")]`/`#[serde(tag
+    /// = "...", content = "...")]` internally/adjacently tagged enums (which are deserialized as
+    /// an ordinary struct/map by the generated code) are unaffected by this setting either way.
+    #[inline]
+    pub fn with_enum_repr_autodetect(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect: true,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that also tries `handler` against ext
+    /// type `type_id` whenever one is decoded without a target type telling us how to interpret
+    /// it, i.e. through `deserialize_any` rather than through a type with a
+    /// `#[serde(deserialize_with = "...")]` field. Decoding into `serde::de::IgnoredAny` skips
+    /// the ext without consulting any handler.
+    ///
+    /// `handler` receives the ext type's raw payload bytes (not including its tag or length
+    /// prefix) and, on success, the decoded value is reported to the caller's `Visitor` via
+    /// [`Visitor::visit_u64`]. Multiple calls register additional handlers rather than replacing
+    /// the previous one; they're tried in registration order, and the first one registered for a
+    /// matching `type_id` wins — later ones for the same `type_id` are never reached.
+    ///
+    /// This generalizes the ad hoc pattern used by
+    /// [`decode::compact_ipv6addr`](crate::decode::compact_ipv6addr), which only kicks in at a
+    /// specific field site named via `#[serde(deserialize_with = "...")]`:
+    /// `with_ext_handler` instead lets a whole class of ext types decode sensibly even when the
+    /// target type isn't known ahead of time, at the cost of only being able to report a `u64`.
+    #[inline]
+    pub fn with_ext_handler<F>(self, type_id: i8, handler: F) -> Deserializer<R, C>
+    where
+        F: Fn(&[u8]) -> Result<u64, Error> + 'static,
+    {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, mut ext_handlers, ext_durations, lenient_map_to_struct_ordering, strict_float_width } = self;
+        ext_handlers.push((type_id, ExtHandler(Box::new(handler))));
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one that reads `std::time::Duration` back
+    /// from the compact ext encoding written by
+    /// [`crate::encode::Serializer::with_ext_durations`] (an 8-byte seconds count followed by a
+    /// 4-byte nanoseconds count), instead of expecting serde's default 2-field struct
+    /// representation.
+    ///
+    /// `Duration`'s `Deserialize` impl lives upstream in `serde` itself, so it cannot be
+    /// special-cased by type; instead [`Self::deserialize_struct`][de::Deserializer::deserialize_struct]
+    /// recognizes the ext tag whenever this option is enabled and the next value is an ext of
+    /// that type, feeding the decoded `secs`/`nanos` pair to the visitor as if it had read a
+    /// 2-element array. A `Duration` encoded the ordinary way is still read back as such: this
+    /// only recognizes the ext form in *addition* to the struct form, it doesn't require it.
+    #[inline]
+    pub fn with_ext_durations(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, lenient_map_to_struct_ordering, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations: true,
+            lenient_map_to_struct_ordering,
+            strict_float_width,
+        }
+    }
+
+    /// Consumes this deserializer and returns a new one where a struct-as-map payload whose keys
+    /// don't name a field of the target struct falls back to matching fields positionally, by the
+    /// order entries appear in the map, instead of leaving them unmatched.
+    ///
+    /// A key that names one of the struct's fields is still matched by name first; only a key
+    /// that doesn't (a plain numeric counter, say, or a field name from an older/newer version of
+    /// the struct) falls back to being bound to whichever field sits at that same position. This
+    /// is meant as a migration aid between a compact (numeric- or positionally-keyed) encoding
+    /// and a named one within the same codebase, not as the default decoding behavior.
+    #[inline]
+    pub fn with_lenient_map_to_struct_ordering(self) -> Deserializer<R, C> {
+        let Deserializer { rd, config, marker, depth, intern, lenient_enums, duplicate_key_policy, bytes_budget, max_array_len, nil_as_empty_collection, float_to_int, tuple_as_map, trailing_optional, unknown_variant_fallback, enum_repr_autodetect, ext_handlers, ext_durations, strict_float_width, .. } = self;
+        Deserializer {
+            rd,
+            config,
+            marker,
+            depth,
+            intern,
+            lenient_enums,
+            duplicate_key_policy,
+            bytes_budget,
+            max_array_len,
+            nil_as_empty_collection,
+            float_to_int,
+            tuple_as_map,
+            trailing_optional,
+            unknown_variant_fallback,
+            enum_repr_autodetect,
+            ext_handlers,
+            ext_durations,
+            lenient_map_to_struct_ordering: true,
+            strict_float_width,
         }
     }
+
+    /// Peeks at the marker of the next value without consuming it.
+    ///
+    /// This lets callers route on a value's shape (e.g. distinguish `map` from `array`) before
+    /// deciding which type to deserialize into. The peeked marker is buffered and returned again
+    /// by the next read, so it's safe to call this any number of times before actually
+    /// deserializing.
+    #[inline]
+    pub fn peek_marker(&mut self) -> Result<Marker, Error> {
+        Ok(self.peek_or_read_marker()?)
+    }
 }
 
 impl<R: AsRef<[u8]>> Deserializer<ReadReader<Cursor<R>>> {
@@ -280,6 +1046,12 @@ where
     R: AsRef<[u8]> + ?Sized,
 {
     /// Constructs a new `Deserializer` from the given byte slice.
+    ///
+    /// The `'de` lifetime of the returned `Deserializer` is tied directly to `rd`, so any
+    /// `&'de str`/`&'de [u8]` field deserialized through it borrows straight out of `rd`'s own
+    /// allocation instead of being copied. The caller must therefore keep `rd` alive for as long
+    /// as the deserialized value (or any borrow derived from it) is used, which the borrow
+    /// checker enforces automatically since both lifetimes are `'de`.
     #[inline(always)]
     pub fn from_read_ref(rd: &'de R) -> Self {
         Deserializer {
@@ -287,6 +1059,21 @@ where
             config: DefaultConfig,
             marker: None,
             depth: 1024,
+            intern: None,
+            lenient_enums: false,
+            duplicate_key_policy: DuplicateKeyPolicy::LastWins,
+            bytes_budget: None,
+            max_array_len: None,
+            nil_as_empty_collection: false,
+            float_to_int: false,
+            tuple_as_map: false,
+            trailing_optional: false,
+            unknown_variant_fallback: false,
+            enum_repr_autodetect: false,
+            ext_handlers: Vec::new(),
+            ext_durations: false,
+            lenient_map_to_struct_ordering: false,
+            strict_float_width: false,
         }
     }
 
@@ -298,15 +1085,135 @@ where
 }
 
 impl<'de, R: ReadSlice<'de>, C: SerializerConfig> Deserializer<R, C> {
-    /// Changes the maximum nesting depth that is allowed
+    /// Changes the maximum nesting depth that is allowed.
+    ///
+    /// This also bounds `#[serde(untagged)]`'s variant-probing: serde buffers the whole payload
+    /// into a `Content` tree before picking a variant, recursing into this `Deserializer` once
+    /// per nesting level exactly like an ordinary nested seq/map would, so a pathological depth
+    /// there errors the same way instead of exhausting the stack or allocating unbounded
+    /// `Content` nodes.
     #[inline(always)]
     pub fn set_max_depth(&mut self, depth: usize) {
         self.depth = depth;
     }
 
+    /// Charges `len` bytes against the remaining budget set by [`Self::with_bytes_limit`],
+    /// erroring instead of letting a single length prefix consume more than what remains.
+    /// A no-op if no limit was configured.
+    fn consume_bytes_budget(&mut self, len: u32) -> Result<(), Error> {
+        if let Some(budget) = self.bytes_budget {
+            let len = len as usize;
+            if len > budget {
+                return Err(Error::LimitExceeded);
+            }
+            self.bytes_budget = Some(budget - len);
+        }
+        Ok(())
+    }
+
+    /// Checks `len` against the cap set by [`Self::with_max_array_len`], erroring before any
+    /// element is read. A no-op if no cap was configured.
+    fn check_array_len(&self, len: u32) -> Result<(), Error> {
+        if let Some(max_len) = self.max_array_len {
+            if len as usize > max_len {
+                return Err(Error::ArrayLenExceeded);
+            }
+        }
+        Ok(())
+    }
+
+    /// Skips exactly one MessagePack value, discarding its bytes without materializing it into
+    /// any Rust value (not even a `String`/`Vec`/collection that would just be thrown away).
+    /// Used by `deserialize_ignored_any` to skip fields the caller doesn't want cheaply.
+    fn skip_value(&mut self) -> Result<(), Error> {
+        let marker = self.take_or_read_marker()?;
+        match marker {
+            Marker::Null | Marker::True | Marker::False => Ok(()),
+            Marker::FixPos(_) | Marker::FixNeg(_) => Ok(()),
+            Marker::U8 | Marker::I8 => self.skip_bytes(1),
+            Marker::U16 | Marker::I16 => self.skip_bytes(2),
+            Marker::U32 | Marker::I32 | Marker::F32 => self.skip_bytes(4),
+            Marker::U64 | Marker::I64 | Marker::F64 => self.skip_bytes(8),
+            Marker::FixStr(len) => self.skip_bytes(len.into()),
+            Marker::Str8 | Marker::Bin8 => {
+                let len: u32 = read_u8(&mut self.rd)?.into();
+                self.skip_bytes(len)
+            }
+            Marker::Str16 | Marker::Bin16 => {
+                let len: u32 = read_u16(&mut self.rd)?.into();
+                self.skip_bytes(len)
+            }
+            Marker::Str32 | Marker::Bin32 => {
+                let len = read_u32(&mut self.rd)?;
+                self.skip_bytes(len)
+            }
+            Marker::FixArray(len) => self.skip_values(len.into()),
+            Marker::Array16 => {
+                let len = read_u16(&mut self.rd)?;
+                self.skip_values(len.into())
+            }
+            Marker::Array32 => {
+                let len = read_u32(&mut self.rd)?;
+                self.skip_values(len)
+            }
+            Marker::FixMap(len) => self.skip_values(u32::from(len) * 2),
+            Marker::Map16 => {
+                let len = read_u16(&mut self.rd)?;
+                self.skip_values(u32::from(len) * 2)
+            }
+            Marker::Map32 => {
+                let len = read_u32(&mut self.rd)?;
+                self.skip_values(len.saturating_mul(2))
+            }
+            Marker::FixExt1 => self.skip_bytes(1 + 1),
+            Marker::FixExt2 => self.skip_bytes(1 + 2),
+            Marker::FixExt4 => self.skip_bytes(1 + 4),
+            Marker::FixExt8 => self.skip_bytes(1 + 8),
+            Marker::FixExt16 => self.skip_bytes(1 + 16),
+            Marker::Ext8 => {
+                let len: u32 = read_u8(&mut self.rd)?.into();
+                self.skip_bytes(1 + len)
+            }
+            Marker::Ext16 => {
+                let len: u32 = read_u16(&mut self.rd)?.into();
+                self.skip_bytes(1 + len)
+            }
+            Marker::Ext32 => {
+                let len = read_u32(&mut self.rd)?;
+                self.skip_bytes(1 + len)
+            }
+            Marker::Reserved => Err(Error::TypeMismatch(marker)),
+        }
+    }
+
+    /// Discards `count` consecutive values, e.g. an array's elements or a map's alternating
+    /// key/value entries.
+    fn skip_values(&mut self, count: u32) -> Result<(), Error> {
+        for _ in 0..count {
+            self.skip_value()?;
+        }
+        Ok(())
+    }
+
+    /// Discards exactly `len` bytes from the underlying reader without buffering all of them at
+    /// once, so skipping a huge string or byte array doesn't allocate proportionally to its size.
+    fn skip_bytes(&mut self, len: u32) -> Result<(), Error> {
+        self.consume_bytes_budget(len)?;
+        let mut remaining = len as usize;
+        let mut buf = [0u8; 256];
+        while remaining > 0 {
+            let chunk = remaining.min(buf.len());
+            self.rd.read_exact(&mut buf[..chunk])
+                .map_err(|err| io_err_to_error(err, chunk, Error::InvalidDataRead))?;
+            remaining -= chunk;
+        }
+        Ok(())
+    }
+
     fn read_str_data<V>(&mut self, len: u32, visitor: V) -> Result<V::Value, Error>
         where V: Visitor<'de>
     {
+        self.consume_bytes_budget(len)?;
         match read_bin_data(&mut self.rd, len as u32)? {
             Reference::Borrowed(buf) => {
                 match str::from_utf8(buf) {
@@ -358,21 +1265,22 @@ impl<'de, R: ReadSlice<'de>, C: SerializerConfig> Deserializer<R, C> {
 }
 
 fn read_bin_data<'a, 'de, R: ReadSlice<'de>>(rd: &'a mut R, len: u32) -> Result<Reference<'de,'a, [u8]>, Error> {
-    rd.read_slice(len as usize).map_err(Error::InvalidDataRead)
+    rd.read_slice(len as usize)
+        .map_err(|err| io_err_to_error(err, len as usize, Error::InvalidDataRead))
 }
 
 fn read_u8<R: Read>(rd: &mut R) -> Result<u8, Error> {
-    byteorder::ReadBytesExt::read_u8(rd).map_err(Error::InvalidDataRead)
+    byteorder::ReadBytesExt::read_u8(rd).map_err(|err| io_err_to_error(err, 1, Error::InvalidDataRead))
 }
 
 fn read_u16<R: Read>(rd: &mut R) -> Result<u16, Error> {
     rd.read_u16::<byteorder::BigEndian>()
-        .map_err(Error::InvalidDataRead)
+        .map_err(|err| io_err_to_error(err, 2, Error::InvalidDataRead))
 }
 
 fn read_u32<R: Read>(rd: &mut R) -> Result<u32, Error> {
     rd.read_u32::<byteorder::BigEndian>()
-        .map_err(Error::InvalidDataRead)
+        .map_err(|err| io_err_to_error(err, 4, Error::InvalidDataRead))
 }
 
 fn ext_len<R: Read>(rd: &mut R, marker: Marker) -> Result<u32, Error> {
@@ -389,9 +1297,76 @@ fn ext_len<R: Read>(rd: &mut R, marker: Marker) -> Result<u32, Error> {
     })
 }
 
+fn array_marker_to_len<R: Read>(rd: &mut R, marker: Marker) -> Result<u32, Error> {
+    Ok(match marker {
+        Marker::FixArray(len) => len.into(),
+        Marker::Array16 => read_u16(rd).map(u32::from)?,
+        Marker::Array32 => read_u32(rd)?,
+        _ => return Err(Error::TypeMismatch(marker)),
+    })
+}
+
+fn str_data_len<R: Read>(rd: &mut R, marker: Marker) -> Result<u32, Error> {
+    Ok(match marker {
+        Marker::FixStr(len) => len.into(),
+        Marker::Str8 => read_u8(rd).map(u32::from)?,
+        Marker::Str16 => read_u16(rd).map(u32::from)?,
+        Marker::Str32 => read_u32(rd)?,
+        _ => return Err(Error::TypeMismatch(marker)),
+    })
+}
+
+fn bin_data_len<R: Read>(rd: &mut R, marker: Marker) -> Result<u32, Error> {
+    Ok(match marker {
+        Marker::Bin8 => read_u8(rd).map(u32::from)?,
+        Marker::Bin16 => read_u16(rd).map(u32::from)?,
+        Marker::Bin32 => read_u32(rd)?,
+        _ => return Err(Error::TypeMismatch(marker)),
+    })
+}
+
+/// Converts a decoded float to an integer type for [`Deserializer::with_float_to_int`],
+/// rejecting a fractional or infinite/NaN value, or one that doesn't fit in `T`, with
+/// [`Error::OutOfRange`].
+fn float_as_int<T>(v: f64) -> Result<T, Error>
+where
+    T: TryFrom<i128>,
+{
+    if !v.is_finite() || v.fract() != 0.0 {
+        return Err(Error::OutOfRange);
+    }
+    T::try_from(v as i128).map_err(|_| Error::OutOfRange)
+}
+
+/// A minimal `Visitor` used to read a map key as an owned `String`, regardless of whether the
+/// underlying reader could have handed out a borrowed `&str`. Used to populate the string
+/// interning table, which must own its entries since they outlive any individual read.
+struct OwnedStrVisitor;
+
+impl<'de> Visitor<'de> for OwnedStrVisitor {
+    type Value = String;
+
+    fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "a string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(v.to_owned())
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+}
+
 #[derive(Debug)]
 enum ExtDeserializerState {
     New,
+    /// Like `New`, but the tag has already been read (by [`Deserializer::deserialize_any`],
+    /// checking it against any handlers registered via [`Deserializer::with_ext_handler`] before
+    /// falling back to the generic `_ExtStruct` newtype shape), so it's reported directly
+    /// instead of being read from `rd` again.
+    TagKnown(i8),
     ReadTag,
     ReadBinary,
 }
@@ -413,6 +1388,18 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> ExtDeserializer<'a, R
             state: ExtDeserializerState::New,
         }
     }
+
+    /// Like [`Self::new`], but for when the caller already read the ext type's tag off `d.rd`
+    /// (to check it against [`Deserializer::with_ext_handler`] registrations) and found no
+    /// matching handler, so it must be reported from `tag` rather than read again.
+    fn with_known_tag(d: &'a mut Deserializer<R, C>, len: u32, tag: i8) -> Self {
+        ExtDeserializer {
+            rd: &mut d.rd,
+            _config: d.config,
+            len,
+            state: ExtDeserializerState::TagKnown(tag),
+        }
+    }
 }
 
 impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::Deserializer<'de> for ExtDeserializer<'a, R, C> {
@@ -441,7 +1428,8 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::SeqAccess<'de> fo
         T: DeserializeSeed<'de>,
     {
         match self.state {
-            ExtDeserializerState::New | ExtDeserializerState::ReadTag => Ok(Some(seed.deserialize(self)?)),
+            ExtDeserializerState::New | ExtDeserializerState::TagKnown(_) | ExtDeserializerState::ReadTag =>
+                Ok(Some(seed.deserialize(self)?)),
             ExtDeserializerState::ReadBinary => Ok(None)
         }
     }
@@ -461,8 +1449,14 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::Deserializer<'de>
                 self.state = ExtDeserializerState::ReadTag;
                 visitor.visit_i8(tag)
             }
+            ExtDeserializerState::TagKnown(tag) => {
+                self.state = ExtDeserializerState::ReadTag;
+                visitor.visit_i8(tag)
+            }
             ExtDeserializerState::ReadTag => {
-                let data = self.rd.read_slice(self.len as usize).map_err(Error::InvalidDataRead)?;
+                let len = self.len as usize;
+                let data = self.rd.read_slice(len)
+                    .map_err(|err| io_err_to_error(err, len, Error::InvalidDataRead))?;
                 self.state = ExtDeserializerState::ReadBinary;
                 match data {
                     Reference::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
@@ -529,13 +1523,21 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
                     Marker::Array32 => read_u32(&mut self.rd)?,
                     _ => unreachable!(),
                 };
+                self.check_array_len(len)?;
 
                 depth_count!(self.depth, {
                     let mut seq = SeqAccess::new(self, len);
                     let res = visitor.visit_seq(&mut seq)?;
                     match seq.left {
                         0 => Ok(res),
-                        excess => Err(Error::LengthMismatch(len - excess)),
+                        excess => {
+                            // The visitor (e.g. a fixed-size `[T; N]` shorter than the
+                            // on-wire array) stopped early. Drain the remaining elements
+                            // so the reader ends up positioned right after the array
+                            // even though we're about to report a length mismatch.
+                            while de::SeqAccess::next_element::<IgnoredAny>(&mut seq)?.is_some() {}
+                            Err(Error::LengthMismatch(len - excess))
+                        }
                     }
                 })
             }
@@ -548,6 +1550,7 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
                     Marker::Map32 => read_u32(&mut self.rd)?,
                     _ => unreachable!()
                 };
+                self.check_array_len(len)?;
 
                 depth_count!(self.depth, {
                     let mut seq = MapAccess::new(self, len);
@@ -559,12 +1562,8 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
                 })
             }
             Marker::Bin8 | Marker::Bin16 | Marker::Bin32 => {
-                let len = match marker {
-                    Marker::Bin8 => read_u8(&mut self.rd).map(u32::from),
-                    Marker::Bin16 => read_u16(&mut self.rd).map(u32::from),
-                    Marker::Bin32 => read_u32(&mut self.rd).map(u32::from),
-                    _ => unreachable!()
-                }?;
+                let len = bin_data_len(&mut self.rd, marker)?;
+                self.consume_bytes_budget(len)?;
                 match read_bin_data(&mut self.rd, len)? {
                     Reference::Borrowed(buf) => visitor.visit_borrowed_bytes(buf),
                     Reference::Copied(buf) => visitor.visit_bytes(buf),
@@ -579,10 +1578,26 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
             Marker::Ext16 |
             Marker::Ext32 => {
                 let len = ext_len(&mut self.rd, marker)?;
-                depth_count!(self.depth, visitor.visit_newtype_struct(ExtDeserializer::new(self, len)))
-            }
-            Marker::Reserved => Err(Error::TypeMismatch(Marker::Reserved)),
-        }
+                self.consume_bytes_budget(len)?;
+
+                let tag = self.rd.read_data_i8()?;
+                let handler = self.ext_handlers.iter()
+                    .find(|(type_id, _)| *type_id == tag)
+                    .map(|(_, handler)| &handler.0);
+                if let Some(handler) = handler {
+                    let data = self.rd.read_slice(len as usize)
+                        .map_err(|err| io_err_to_error(err, len as usize, Error::InvalidDataRead))?;
+                    let value = match data {
+                        Reference::Borrowed(bytes) => handler(bytes)?,
+                        Reference::Copied(bytes) => handler(bytes)?,
+                    };
+                    return visitor.visit_u64(value);
+                }
+
+                depth_count!(self.depth, visitor.visit_newtype_struct(ExtDeserializer::with_known_tag(self, len, tag)))
+            }
+            Marker::Reserved => Err(Error::TypeMismatch(Marker::Reserved)),
+        }
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -612,7 +1627,7 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
         }
     }
 
-    fn deserialize_enum<V>(self, _name: &str, _variants: &[&str], visitor: V) -> Result<V::Value, Error>
+    fn deserialize_enum<V>(self, _name: &str, variants: &[&str], visitor: V) -> Result<V::Value, Error>
         where V: Visitor<'de>
     {
         let marker = self.peek_or_read_marker()?;
@@ -623,12 +1638,24 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
                 // or as just the variant
                 1 => {
                     self.marker = None;
-                    visitor.visit_enum(VariantAccess::new(self))
+                    visitor.visit_enum(VariantAccess::new(self, variants))
                 }
                 n => Err(Error::LengthMismatch(n as u32)),
             },
+            // Not a map. If lenient enums are enabled, also accept the 2-element array form
+            // `[variant, data]` some other MessagePack producers use instead of a 1-entry map.
+            // `VariantAccess` reads the identifier followed by the data either way, so it does
+            // not need to know which container shape it came from.
+            Err(_) if self.lenient_enums || self.enum_repr_autodetect => match array_marker_to_len(&mut self.rd, marker) {
+                Ok(2) => {
+                    self.marker = None;
+                    visitor.visit_enum(VariantAccess::new(self, variants))
+                }
+                Ok(n) => Err(Error::LengthMismatch(n)),
+                Err(_) => visitor.visit_enum(UnitVariantAccess::new(self, variants)),
+            },
             // TODO: Check this is a string
-            Err(_) => visitor.visit_enum(UnitVariantAccess::new(self)),
+            Err(_) => visitor.visit_enum(UnitVariantAccess::new(self, variants)),
         }
     }
 
@@ -646,6 +1673,21 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
         visitor.visit_newtype_struct(self)
     }
 
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        // Accept both representations a `Serializer` can produce for `()`: `nil` by default, or
+        // `[]` under `Serializer::with_unit_as_empty_array`. Falls through otherwise, same as
+        // `deserialize_unit_struct` below.
+        match self.take_or_read_marker()? {
+            Marker::Null | Marker::FixArray(0) => visitor.visit_unit(),
+            marker => {
+                self.marker = Some(marker);
+                self.deserialize_any(visitor)
+            }
+        }
+    }
+
     fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
         where V: Visitor<'de>
     {
@@ -661,11 +1703,77 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
         }
     }
 
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        // Same special-casing shape as `deserialize_unit_struct`: peek the marker, and only
+        // divert from the normal path (here, to synthesize a zero-length seq) for the one marker
+        // this flag cares about, falling through to `deserialize_any` otherwise.
+        if self.nil_as_empty_collection {
+            match self.take_or_read_marker()? {
+                Marker::Null => return visitor.visit_seq(SeqAccess::new(self, 0)),
+                marker => self.marker = Some(marker),
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        if self.nil_as_empty_collection {
+            match self.take_or_read_marker()? {
+                Marker::Null => return visitor.visit_map(MapAccess::new(self, 0)),
+                marker => self.marker = Some(marker),
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        // Bypasses `deserialize_any` for the one marker family it's worth special-casing: a
+        // `bin` payload can be handed straight to `visit_byte_buf` as a single owned `Vec<u8>`,
+        // rather than round-tripping through a borrowed/copied slice the visitor would then have
+        // to copy again itself.
+        match self.take_or_read_marker()? {
+            marker @ (Marker::Bin8 | Marker::Bin16 | Marker::Bin32) => {
+                let len = bin_data_len(&mut self.rd, marker)?;
+                self.consume_bytes_budget(len)?;
+                let buf = match read_bin_data(&mut self.rd, len)? {
+                    Reference::Borrowed(buf) => buf.to_vec(),
+                    Reference::Copied(buf) => buf.to_vec(),
+                };
+                visitor.visit_byte_buf(buf)
+            }
+            marker => {
+                self.marker = Some(marker);
+                self.deserialize_any(visitor)
+            }
+        }
+    }
+
     #[inline]
     fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        // Mirrors `Serializer::serialize_i128`: human-readable mode writes (and reads back) a
+        // decimal string instead of the compact 16-byte `bin` representation.
+        if self.is_human_readable() {
+            struct I128StrVisitor;
+            impl<'de> Visitor<'de> for I128StrVisitor {
+                type Value = i128;
+                fn expecting(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+                    fmt.write_str("a string containing a 128-bit signed integer")
+                }
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    v.parse().map_err(|_| de::Error::invalid_value(de::Unexpected::Str(v), &"a 128-bit signed integer"))
+                }
+            }
+            return visitor.visit_i128(self.deserialize_str(I128StrVisitor)?);
+        }
         let buf = self.read_128()?;
         visitor.visit_i128(i128::from_be_bytes(buf))
     }
@@ -675,15 +1783,203 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> serde::Deserializer<'de> f
     where
         V: Visitor<'de>,
     {
+        if self.is_human_readable() {
+            struct U128StrVisitor;
+            impl<'de> Visitor<'de> for U128StrVisitor {
+                type Value = u128;
+                fn expecting(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+                    fmt.write_str("a string containing a 128-bit unsigned integer")
+                }
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    v.parse().map_err(|_| de::Error::invalid_value(de::Unexpected::Str(v), &"a 128-bit unsigned integer"))
+                }
+            }
+            return visitor.visit_u128(self.deserialize_str(U128StrVisitor)?);
+        }
         let buf = self.read_128()?;
         visitor.visit_u128(u128::from_be_bytes(buf))
     }
 
+    deserialize_int_or_float!(deserialize_u8, visit_u8);
+    deserialize_int_or_float!(deserialize_u16, visit_u16);
+    deserialize_int_or_float!(deserialize_u32, visit_u32);
+    deserialize_int_or_float!(deserialize_u64, visit_u64);
+    deserialize_int_or_float!(deserialize_i8, visit_i8);
+    deserialize_int_or_float!(deserialize_i16, visit_i16);
+    deserialize_int_or_float!(deserialize_i32, visit_i32);
+    deserialize_int_or_float!(deserialize_i64, visit_i64);
+
+    /// Under [`Self::with_strict_float_width`], errors if the next value is a `float64` instead
+    /// of narrowing it to `f32`.
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        if self.strict_float_width {
+            if let Marker::F64 = self.peek_or_read_marker()? {
+                return Err(Error::TypeMismatch(Marker::F64));
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    /// Under [`Self::with_strict_float_width`], errors if the next value is a `float32` instead
+    /// of widening it up to `f64`.
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        if self.strict_float_width {
+            if let Marker::F32 = self.peek_or_read_marker()? {
+                return Err(Error::TypeMismatch(Marker::F32));
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        // Same special-casing shape as `deserialize_seq`: only a tuple serialized under
+        // `with_empty_tuple_as_nil` reaches the wire as `nil`, so that's the only marker this
+        // flag needs to divert here.
+        if self.nil_as_empty_collection {
+            match self.take_or_read_marker()? {
+                Marker::Null => return visitor.visit_seq(SeqAccess::new(self, 0)),
+                marker => self.marker = Some(marker),
+            }
+        }
+
+        // Likewise, only `with_tuple_as_map` can have produced a map here, so that's the only
+        // other marker family this needs to divert.
+        if self.tuple_as_map {
+            match self.take_or_read_marker()? {
+                marker @ (Marker::FixMap(_) | Marker::Map16 | Marker::Map32) => {
+                    let len = match marker {
+                        Marker::FixMap(len) => len.into(),
+                        Marker::Map16 => read_u16(&mut self.rd)?.into(),
+                        Marker::Map32 => read_u32(&mut self.rd)?,
+                        _ => unreachable!(),
+                    };
+                    self.check_array_len(len)?;
+                    return visitor.visit_seq(TupleMapAccess::new(self, len));
+                }
+                marker => self.marker = Some(marker),
+            }
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V>(self, name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        // `Duration`'s `Deserialize` impl is `serde`'s own, so it can't be special-cased by type
+        // like `Timestamp` is; it's recognized here by name instead, matching the encoding side's
+        // `Serializer::serialize_struct`. Only the ext form written by `with_ext_durations` is
+        // diverted — an ordinary struct/array-shaped `Duration` still falls through to
+        // `deserialize_any` below exactly as it always has.
+        if self.ext_durations && name == "Duration" {
+            if let Marker::FixExt1 | Marker::FixExt2 | Marker::FixExt4 | Marker::FixExt8 |
+                Marker::FixExt16 | Marker::Ext8 | Marker::Ext16 | Marker::Ext32 = self.peek_or_read_marker()?
+            {
+                let marker = self.take_or_read_marker()?;
+                let len = ext_len(&mut self.rd, marker)?;
+                self.consume_bytes_budget(len)?;
+
+                let tag = self.rd.read_data_i8()?;
+                if tag != DURATION_EXT_TYPE {
+                    return Err(de::Error::custom(format_args!(
+                        "expected Duration ext type {}, found {}", DURATION_EXT_TYPE, tag,
+                    )));
+                }
+                if len != 12 {
+                    return Err(Error::LengthMismatch(12));
+                }
+
+                let secs = self.rd.read_data_u64()?;
+                let nanos = self.rd.read_data_u32()?;
+                return depth_count!(self.depth, visitor.visit_seq(&mut DurationExtSeqAccess {
+                    secs,
+                    nanos,
+                    state: DurationExtSeqState::Secs,
+                }));
+            }
+        }
+
+        // Only a struct-as-tuple array can benefit from `with_trailing_optional`; a struct-as-map
+        // payload already lets any key be omitted, so this only needs to divert the array marker
+        // families, mirroring `deserialize_tuple`'s own diversions above.
+        if self.trailing_optional {
+            match self.take_or_read_marker()? {
+                marker @ (Marker::FixArray(_) | Marker::Array16 | Marker::Array32) => {
+                    let len = match marker {
+                        Marker::FixArray(len) => len.into(),
+                        Marker::Array16 => read_u16(&mut self.rd)?.into(),
+                        Marker::Array32 => read_u32(&mut self.rd)?,
+                        _ => unreachable!(),
+                    };
+                    self.check_array_len(len)?;
+                    return depth_count!(self.depth, {
+                        let mut seq = LenientSeqAccess::new(self, len);
+                        let res = visitor.visit_seq(&mut seq)?;
+                        match seq.left {
+                            0 => Ok(res),
+                            excess => {
+                                while de::SeqAccess::next_element::<IgnoredAny>(&mut seq)?.is_some() {}
+                                Err(Error::LengthMismatch(len - excess))
+                            }
+                        }
+                    });
+                }
+                marker => self.marker = Some(marker),
+            }
+        }
+
+        // A key naming one of `fields` is still matched by name; anything else (a numeric key,
+        // or a string that doesn't name a field) falls back to matching positionally instead, by
+        // the order entries appear in the map rather than by what the key actually says.
+        if self.lenient_map_to_struct_ordering && !fields.is_empty() {
+            match self.take_or_read_marker()? {
+                marker @ (Marker::FixMap(_) | Marker::Map16 | Marker::Map32) => {
+                    let len = match marker {
+                        Marker::FixMap(len) => len.into(),
+                        Marker::Map16 => read_u16(&mut self.rd)?.into(),
+                        Marker::Map32 => read_u32(&mut self.rd)?,
+                        _ => unreachable!(),
+                    };
+                    self.check_array_len(len)?;
+                    return depth_count!(self.depth, {
+                        let mut map = LenientStructMapAccess::new(self, len, fields);
+                        let res = visitor.visit_map(&mut map)?;
+                        match map.left {
+                            0 => Ok(res),
+                            excess => Err(Error::LengthMismatch(len - excess)),
+                        }
+                    });
+                }
+                marker => self.marker = Some(marker),
+            }
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    #[inline]
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        self.skip_value()?;
+        visitor.visit_unit()
+    }
+
     forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32
-        f64 char str string bytes byte_buf unit
-        seq map struct identifier tuple
-        tuple_struct ignored_any
+        bool char str string bytes
+        identifier
     }
 }
 
@@ -723,16 +2019,271 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::SeqAccess<'de> fo
     }
 }
 
+/// Feeds a decoded [`Deserializer::with_ext_durations`] `secs`/`nanos` pair to `serde`'s own
+/// `Duration` `Visitor`, which reads them via `visit_seq` exactly as if they had come from a
+/// 2-element array.
+enum DurationExtSeqState {
+    Secs,
+    Nanos,
+    Done,
+}
+
+struct DurationExtSeqAccess {
+    secs: u64,
+    nanos: u32,
+    state: DurationExtSeqState,
+}
+
+impl<'de> de::SeqAccess<'de> for DurationExtSeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+        where T: DeserializeSeed<'de>
+    {
+        match self.state {
+            DurationExtSeqState::Secs => {
+                self.state = DurationExtSeqState::Nanos;
+                seed.deserialize(serde::de::value::U64Deserializer::new(self.secs)).map(Some)
+            }
+            DurationExtSeqState::Nanos => {
+                self.state = DurationExtSeqState::Done;
+                seed.deserialize(serde::de::value::U32Deserializer::new(self.nanos)).map(Some)
+            }
+            DurationExtSeqState::Done => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        match self.state {
+            DurationExtSeqState::Secs => Some(2),
+            DurationExtSeqState::Nanos => Some(1),
+            DurationExtSeqState::Done => Some(0),
+        }
+    }
+}
+
+/// Reads a struct-as-tuple array under [`Deserializer::with_trailing_optional`], tolerating an
+/// on-wire array shorter than the number of elements the visitor asks for: once the real elements
+/// are exhausted, each further element is produced by [`MissingFieldDeserializer`], which only
+/// `Option<T>` fields (via `deserialize_option`) can actually consume. A non-optional field
+/// still sees the missing element as `None` from `next_element_seed`, which lets `#[serde(default)]`
+/// fields fall back to their default exactly as [`SeqAccess`] already does, and lets any other
+/// required field error with the usual "invalid length" message.
+struct LenientSeqAccess<'a, R, C> {
+    de: &'a mut Deserializer<R, C>,
+    left: u32,
+}
+
+impl<'a, R: 'a, C> LenientSeqAccess<'a, R, C> {
+    #[inline]
+    fn new(de: &'a mut Deserializer<R, C>, len: u32) -> Self {
+        LenientSeqAccess { de, left: len }
+    }
+}
+
+impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::SeqAccess<'de> for LenientSeqAccess<'a, R, C> {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+        where T: DeserializeSeed<'de>
+    {
+        if self.left > 0 {
+            self.left -= 1;
+            Ok(Some(seed.deserialize(&mut *self.de)?))
+        } else {
+            match seed.deserialize(MissingFieldDeserializer) {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => Ok(None),
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> Option<usize> {
+        self.left.try_into().ok()
+    }
+}
+
+/// Stands in for an array element that [`LenientSeqAccess`] ran out of real elements for.
+///
+/// It answers `deserialize_option` with `None` (so an `Option<T>` field can consume it), and
+/// errors on every other method, so [`LenientSeqAccess`] falls back to its usual "no more
+/// elements" behavior for any field that isn't optional.
+struct MissingFieldDeserializer;
+
+impl<'de> de::Deserializer<'de> for MissingFieldDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        Err(de::Error::custom("trailing field missing"))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        visitor.visit_none()
+    }
+
+    forward_to_deserialize_any! {
+        bool u8 u16 u32 u64 i8 i16 i32 i64 i128 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+/// Reads a tuple/tuple-struct that was written under [`Serializer::with_tuple_as_map`], i.e. as
+/// a map of integer keys `0..n` to element values, presenting it to the visitor as a plain
+/// sequence.
+///
+/// [`Serializer::with_tuple_as_map`]: crate::encode::Serializer::with_tuple_as_map
+struct TupleMapAccess<'a, R, C> {
+    de: &'a mut Deserializer<R, C>,
+    left: u32,
+}
+
+impl<'a, R: 'a, C> TupleMapAccess<'a, R, C> {
+    #[inline]
+    fn new(de: &'a mut Deserializer<R, C>, len: u32) -> Self {
+        TupleMapAccess {
+            de,
+            left: len,
+        }
+    }
+}
+
+impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::SeqAccess<'de> for TupleMapAccess<'a, R, C> {
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+        where T: DeserializeSeed<'de>
+    {
+        if self.left > 0 {
+            self.left -= 1;
+            // The key is always the element's own position; nothing to do with it besides
+            // consuming its bytes.
+            IgnoredAny::deserialize(&mut *self.de)?;
+            Ok(Some(seed.deserialize(&mut *self.de)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> Option<usize> {
+        self.left.try_into().ok()
+    }
+}
+
 struct MapAccess<'a, R, C> {
     de: &'a mut Deserializer<R, C>,
     left: u32,
+    /// String keys seen so far, tracked whenever `Deserializer::duplicate_key_policy` is anything
+    /// other than [`DuplicateKeyPolicy::LastWins`] (which needs no tracking, since it just lets
+    /// every occurrence through unchanged).
+    seen_keys: Option<HashSet<String>>,
 }
 
 impl<'a, R: 'a, C> MapAccess<'a, R, C> {
     fn new(de: &'a mut Deserializer<R, C>, len: u32) -> Self {
+        let seen_keys = if de.duplicate_key_policy == DuplicateKeyPolicy::LastWins {
+            None
+        } else {
+            Some(HashSet::new())
+        };
         MapAccess {
             de,
             left: len,
+            seen_keys,
+        }
+    }
+}
+
+impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> MapAccess<'a, R, C> {
+    /// Reads a map key that may be either an ordinary string or, when string interning is
+    /// enabled, a back-reference to a previously read one.
+    ///
+    /// A plain string key is recorded into the interning table (so a later back-reference can
+    /// resolve it) before being handed to `seed`. A `STRING_INTERN_EXT_TYPE` ext key is resolved
+    /// against the table and the resulting name is handed to `seed` as if it had been read as a
+    /// string directly.
+    fn next_interned_key<K>(&mut self, seed: K) -> Result<K::Value, Error>
+        where K: DeserializeSeed<'de>
+    {
+        let marker = self.de.peek_or_read_marker()?;
+        if marker == Marker::FixExt1 {
+            self.de.take_or_read_marker()?;
+            let tag = self.de.rd.read_data_i8()?;
+            if tag != STRING_INTERN_EXT_TYPE {
+                return Err(Error::TypeMismatch(marker));
+            }
+            let idx = match read_bin_data(&mut self.de.rd, 1)? {
+                Reference::Borrowed(buf) => buf[0],
+                Reference::Copied(buf) => buf[0],
+            };
+            let name = self.de.intern.as_ref()
+                .and_then(|table| table.get(idx as usize))
+                .ok_or_else(|| Error::Uncategorized(format!("unknown string intern index {}", idx)))?
+                .clone();
+            seed.deserialize(de::value::StrDeserializer::new(&name))
+        } else {
+            self.de.take_or_read_marker()?;
+            let len = str_data_len(&mut self.de.rd, marker)?;
+            let name = self.de.read_str_data(len, OwnedStrVisitor)?;
+            self.de.intern.as_mut().expect("interning enabled").push(name.clone());
+            seed.deserialize(de::value::StrDeserializer::new(&name))
+        }
+    }
+
+    /// Reads a map key that may be a string already seen earlier in this same map, handling the
+    /// repeat according to [`Deserializer::duplicate_key_policy`] (either [`Error`] or
+    /// [`FirstWins`], since [`MapAccess::new`] only tracks `seen_keys` for those two policies).
+    /// Loops past any number of consecutive [`FirstWins`]-discarded duplicates until it finds a
+    /// fresh key or runs out of entries.
+    ///
+    /// [`Error`]: DuplicateKeyPolicy::Error
+    /// [`FirstWins`]: DuplicateKeyPolicy::FirstWins
+    fn next_checked_key<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+        where K: DeserializeSeed<'de>
+    {
+        loop {
+            let marker = self.de.peek_or_read_marker()?;
+            let is_str = matches!(
+                marker,
+                Marker::FixStr(_) | Marker::Str8 | Marker::Str16 | Marker::Str32
+            );
+            if !is_str {
+                return seed.deserialize(&mut *self.de).map(Some);
+            }
+
+            self.de.take_or_read_marker()?;
+            let len = str_data_len(&mut self.de.rd, marker)?;
+            let name = self.de.read_str_data(len, OwnedStrVisitor)?;
+
+            let seen_keys = self.seen_keys.as_mut().expect("duplicate key checking enabled");
+            if !seen_keys.insert(name.clone()) {
+                match self.de.duplicate_key_policy {
+                    DuplicateKeyPolicy::Error => {
+                        return Err(Error::Uncategorized(format!("duplicate map key: {:?}", name)));
+                    }
+                    DuplicateKeyPolicy::FirstWins => {
+                        self.de.skip_value()?;
+                        if self.left == 0 {
+                            return Ok(None);
+                        }
+                        self.left -= 1;
+                        continue;
+                    }
+                    DuplicateKeyPolicy::LastWins => {
+                        unreachable!("seen_keys is only tracked for Error/FirstWins")
+                    }
+                }
+            }
+            return seed.deserialize(de::value::StrDeserializer::new(&name)).map(Some);
         }
     }
 }
@@ -744,11 +2295,82 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::MapAccess<'de> fo
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
         where K: DeserializeSeed<'de>
     {
-        if self.left > 0 {
-            self.left -= 1;
+        if self.left == 0 {
+            return Ok(None);
+        }
+        self.left -= 1;
+
+        if self.de.intern.is_some() {
+            self.next_interned_key(seed).map(Some)
+        } else if self.seen_keys.is_some() {
+            self.next_checked_key(seed)
+        } else {
             seed.deserialize(&mut *self.de).map(Some)
+        }
+    }
+
+    #[inline]
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+        where V: DeserializeSeed<'de>
+    {
+        seed.deserialize(&mut *self.de)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> Option<usize> {
+        self.left.try_into().ok()
+    }
+}
+
+/// `MapAccess` used by [`Deserializer::with_lenient_map_to_struct_ordering`] when decoding a
+/// struct from a map. A key that names one of `fields` is still matched by name; any other key
+/// (a numeric key, or a string that isn't one of `fields`) falls back to matching whichever field
+/// sits at `position` in `fields`, `position` being a running count of entries seen so far rather
+/// than anything derived from the key itself.
+struct LenientStructMapAccess<'a, R, C> {
+    de: &'a mut Deserializer<R, C>,
+    left: u32,
+    fields: &'static [&'static str],
+    position: usize,
+}
+
+impl<'a, R: 'a, C> LenientStructMapAccess<'a, R, C> {
+    fn new(de: &'a mut Deserializer<R, C>, len: u32, fields: &'static [&'static str]) -> Self {
+        LenientStructMapAccess { de, left: len, fields, position: 0 }
+    }
+}
+
+impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::MapAccess<'de> for LenientStructMapAccess<'a, R, C> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+        where K: DeserializeSeed<'de>
+    {
+        if self.left == 0 {
+            return Ok(None);
+        }
+        self.left -= 1;
+
+        let marker = self.de.peek_or_read_marker()?;
+        let is_str = matches!(marker, Marker::FixStr(_) | Marker::Str8 | Marker::Str16 | Marker::Str32);
+        let matched_name = if is_str {
+            self.de.take_or_read_marker()?;
+            let len = str_data_len(&mut self.de.rd, marker)?;
+            let name = self.de.read_str_data(len, OwnedStrVisitor)?;
+            self.fields.contains(&name.as_str()).then_some(name)
         } else {
-            Ok(None)
+            self.de.skip_value()?;
+            None
+        };
+
+        let position = self.position;
+        self.position += 1;
+        match matched_name {
+            Some(name) => seed.deserialize(de::value::StrDeserializer::new(&name)).map(Some),
+            None => {
+                let name = self.fields.get(position).copied().unwrap_or("");
+                seed.deserialize(de::value::StrDeserializer::new(name)).map(Some)
+            }
         }
     }
 
@@ -765,18 +2387,50 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::MapAccess<'de> fo
     }
 }
 
-struct UnitVariantAccess<'a, R: 'a, C> {
+/// Reads an externally-tagged enum's variant-name identifier, substituting the literal name
+/// `"other"` for an unrecognized name when [`Deserializer::with_unknown_variant_fallback`] is
+/// enabled, so a target enum variant serialized as `"other"` can catch it. Falls through to the
+/// ordinary identifier decoding otherwise.
+fn deserialize_variant_identifier<'de, R, C, V>(
+    de: &mut Deserializer<R, C>,
+    variants: &[&str],
+    seed: V,
+) -> Result<V::Value, Error>
+where
+    R: ReadSlice<'de>,
+    C: SerializerConfig,
+    V: DeserializeSeed<'de>,
+{
+    if de.unknown_variant_fallback && !variants.is_empty() {
+        let marker = de.peek_or_read_marker()?;
+        let is_str = matches!(
+            marker,
+            Marker::FixStr(_) | Marker::Str8 | Marker::Str16 | Marker::Str32
+        );
+        if is_str {
+            de.take_or_read_marker()?;
+            let len = str_data_len(&mut de.rd, marker)?;
+            let name = de.read_str_data(len, OwnedStrVisitor)?;
+            let name = if variants.contains(&name.as_str()) { name } else { "other".to_owned() };
+            return seed.deserialize(de::value::StrDeserializer::new(&name));
+        }
+    }
+    seed.deserialize(de)
+}
+
+struct UnitVariantAccess<'a, 'b, R: 'a, C> {
     de: &'a mut Deserializer<R, C>,
+    variants: &'b [&'b str],
 }
 
-impl<'a, R: 'a, C> UnitVariantAccess<'a, R, C> {
-    pub fn new(de: &'a mut Deserializer<R, C>) -> Self {
-        UnitVariantAccess { de }
+impl<'a, 'b, R: 'a, C> UnitVariantAccess<'a, 'b, R, C> {
+    pub fn new(de: &'a mut Deserializer<R, C>, variants: &'b [&'b str]) -> Self {
+        UnitVariantAccess { de, variants }
     }
 }
 
-impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> de::EnumAccess<'de>
-    for UnitVariantAccess<'a, R, C>
+impl<'de, 'a, 'b, R: ReadSlice<'de>, C: SerializerConfig> de::EnumAccess<'de>
+    for UnitVariantAccess<'a, 'b, R, C>
 {
     type Error = Error;
     type Variant = Self;
@@ -786,13 +2440,13 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> de::EnumAccess<'de>
     where
         V: de::DeserializeSeed<'de>,
     {
-        let variant = seed.deserialize(&mut *self.de)?;
+        let variant = deserialize_variant_identifier(self.de, self.variants, seed)?;
         Ok((variant, self))
     }
 }
 
-impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::VariantAccess<'de>
-    for UnitVariantAccess<'a, R, C>
+impl<'de, 'a, 'b, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::VariantAccess<'de>
+    for UnitVariantAccess<'a, 'b, R, C>
 {
     type Error = Error;
 
@@ -835,17 +2489,18 @@ impl<'de, 'a, R: ReadSlice<'de> + 'a, C: SerializerConfig> de::VariantAccess<'de
     }
 }
 
-struct VariantAccess<'a, R, C> {
+struct VariantAccess<'a, 'b, R, C> {
     de: &'a mut Deserializer<R, C>,
+    variants: &'b [&'b str],
 }
 
-impl<'a, R: 'a, C> VariantAccess<'a, R, C> {
-    pub fn new(de: &'a mut Deserializer<R, C>) -> Self {
-        VariantAccess { de }
+impl<'a, 'b, R: 'a, C> VariantAccess<'a, 'b, R, C> {
+    pub fn new(de: &'a mut Deserializer<R, C>, variants: &'b [&'b str]) -> Self {
+        VariantAccess { de, variants }
     }
 }
 
-impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> de::EnumAccess<'de> for VariantAccess<'a, R, C> {
+impl<'de, 'a, 'b, R: ReadSlice<'de>, C: SerializerConfig> de::EnumAccess<'de> for VariantAccess<'a, 'b, R, C> {
     type Error = Error;
     type Variant = Self;
 
@@ -853,11 +2508,11 @@ impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> de::EnumAccess<'de> for Va
     fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
         where V: de::DeserializeSeed<'de>,
     {
-        Ok((seed.deserialize(&mut *self.de)?, self))
+        Ok((deserialize_variant_identifier(self.de, self.variants, seed)?, self))
     }
 }
 
-impl<'de, 'a, R: ReadSlice<'de>, C: SerializerConfig> de::VariantAccess<'de> for VariantAccess<'a, R, C> {
+impl<'de, 'a, 'b, R: ReadSlice<'de>, C: SerializerConfig> de::VariantAccess<'de> for VariantAccess<'a, 'b, R, C> {
     type Error = Error;
 
     #[inline]
@@ -1068,3 +2723,148 @@ where
     let mut de = Deserializer::from_read_ref(rd);
     Deserialize::deserialize(&mut de)
 }
+
+/// Deserializes a `Cow<'de, str>` field, borrowing from the input instead of allocating whenever
+/// the underlying reader hands back a `&'de str` directly.
+///
+/// Serde's blanket `Cow<'a, T>` impl always deserializes through `T::Owned`, so a plain
+/// `#[derive(Deserialize)]` field typed `Cow<'de, str>` never borrows even when reading from a
+/// slice with [`from_slice`]. Opt a field in with:
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// #[derive(serde_derive::Deserialize)]
+/// struct Message<'a> {
+///     #[serde(borrow, deserialize_with = "rmp_serde::decode::borrow_cow_str")]
+///     text: Cow<'a, str>,
+/// }
+/// ```
+pub fn borrow_cow_str<'de, D>(deserializer: D) -> Result<std::borrow::Cow<'de, str>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    struct CowStrVisitor;
+
+    impl<'de> Visitor<'de> for CowStrVisitor {
+        type Value = std::borrow::Cow<'de, str>;
+
+        fn expecting(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+            fmt.write_str("a string")
+        }
+
+        fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+            Ok(std::borrow::Cow::Borrowed(v))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(std::borrow::Cow::Owned(v.to_owned()))
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+            Ok(std::borrow::Cow::Owned(v))
+        }
+    }
+
+    deserializer.deserialize_str(CowStrVisitor)
+}
+
+/// Deserializes an [`Ipv4Addr`](std::net::Ipv4Addr) previously encoded with
+/// [`encode::compact_ipv4addr`](crate::encode::compact_ipv4addr), reading it back from a 4-byte
+/// `bin` instead of serde's default 4-element tuple of octets.
+///
+/// ```
+/// use std::net::Ipv4Addr;
+///
+/// #[derive(serde_derive::Deserialize)]
+/// struct Peer {
+///     #[serde(deserialize_with = "rmp_serde::decode::compact_ipv4addr")]
+///     addr: Ipv4Addr,
+/// }
+/// ```
+pub fn compact_ipv4addr<'de, D>(deserializer: D) -> Result<std::net::Ipv4Addr, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    struct Ipv4Visitor;
+
+    impl<'de> Visitor<'de> for Ipv4Visitor {
+        type Value = std::net::Ipv4Addr;
+
+        fn expecting(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+            fmt.write_str("4 bytes containing an IPv4 address")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            let octets: [u8; 4] = v.try_into().map_err(|_| {
+                de::Error::invalid_length(v.len(), &"4 bytes")
+            })?;
+            Ok(std::net::Ipv4Addr::from(octets))
+        }
+    }
+
+    deserializer.deserialize_bytes(Ipv4Visitor)
+}
+
+/// Deserializes an [`Ipv6Addr`](std::net::Ipv6Addr) previously encoded with
+/// [`encode::compact_ipv6addr`](crate::encode::compact_ipv6addr), reading it back from a 16-byte
+/// `bin` instead of serde's default 16-element tuple of octets.
+pub fn compact_ipv6addr<'de, D>(deserializer: D) -> Result<std::net::Ipv6Addr, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    struct Ipv6Visitor;
+
+    impl<'de> Visitor<'de> for Ipv6Visitor {
+        type Value = std::net::Ipv6Addr;
+
+        fn expecting(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+            fmt.write_str("16 bytes containing an IPv6 address")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            let octets: [u8; 16] = v.try_into().map_err(|_| {
+                de::Error::invalid_length(v.len(), &"16 bytes")
+            })?;
+            Ok(std::net::Ipv6Addr::from(octets))
+        }
+    }
+
+    deserializer.deserialize_bytes(Ipv6Visitor)
+}
+
+/// Deserializes a `SmallVec<[u8; N]>` previously encoded with
+/// [`encode::compact_smallvec_bytes`](crate::encode::compact_smallvec_bytes), reading it back
+/// from a `bin` instead of smallvec's own `Deserialize` impl (which expects a plain seq).
+///
+/// ```
+/// use smallvec::SmallVec;
+///
+/// #[derive(serde_derive::Deserialize)]
+/// struct Frame {
+///     #[serde(deserialize_with = "rmp_serde::decode::compact_smallvec_bytes")]
+///     payload: SmallVec<[u8; 16]>,
+/// }
+/// ```
+#[cfg(feature = "smallvec")]
+pub fn compact_smallvec_bytes<'de, D, A>(deserializer: D) -> Result<smallvec::SmallVec<A>, D::Error>
+where
+    D: de::Deserializer<'de>,
+    A: smallvec::Array<Item = u8>,
+{
+    struct SmallVecBytesVisitor<A>(std::marker::PhantomData<A>);
+
+    impl<'de, A: smallvec::Array<Item = u8>> Visitor<'de> for SmallVecBytesVisitor<A> {
+        type Value = smallvec::SmallVec<A>;
+
+        fn expecting(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+            fmt.write_str("bytes containing a SmallVec<[u8; N]>")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(smallvec::SmallVec::from_slice(v))
+        }
+    }
+
+    deserializer.deserialize_bytes(SmallVecBytesVisitor(std::marker::PhantomData))
+}
@@ -1,5 +1,6 @@
 //! Serialize a Rust data structure into MessagePack data.
 
+use std::collections::HashMap;
 use std::error;
 use std::fmt::{self, Display};
 use std::io::Write;
@@ -15,11 +16,24 @@ use rmp::encode::ValueWriteError;
 use rmp::{encode, Marker};
 
 use crate::config::{
-    BinaryConfig, DefaultConfig, HumanReadableConfig, SerializerConfig, StructMapConfig,
-    StructTupleConfig
+    BinaryConfig, DefaultConfig, HumanReadableConfig, IntegerVariantConfig, SerializerConfig,
+    StringInterningConfig, StructMapConfig, StructMapSortedConfig, StructTupleConfig
 };
 use crate::MSGPACK_EXT_STRUCT_NAME;
 
+/// Ext type tag used by [`Serializer::with_string_interning`] to reference a previously seen
+/// struct field name by index. This is a non-standard, application-specific extension (non-negative
+/// ext type tags, 0 to 127, are reserved by the MessagePack spec for application use, as opposed to the
+/// negative tags reserved for spec-defined extensions like Timestamp); only a decoder with the
+/// matching `Deserializer::with_string_interning` option enabled can read it back.
+pub const STRING_INTERN_EXT_TYPE: i8 = 0;
+
+/// Ext type tag used by [`Serializer::with_ext_durations`] to encode a `std::time::Duration` as
+/// an 8-byte seconds count followed by a 4-byte nanoseconds count, instead of serde's default
+/// 2-field struct. Another non-standard, application-specific extension; only a decoder with the
+/// matching `Deserializer::with_ext_durations` option enabled can read it back.
+pub const DURATION_EXT_TYPE: i8 = 1;
+
 /// This type represents all possible errors that can occur when serializing or
 /// deserializing MessagePack data.
 #[derive(Debug)]
@@ -33,6 +47,11 @@ pub enum Error {
     InvalidDataModel(&'static str),
     /// Depth limit exceeded
     DepthLimitExceeded,
+    /// A `Serialize` impl declared a different `serialize_seq`/`serialize_map` length than the
+    /// number of elements it went on to actually serialize. Encloses the declared and actual
+    /// element counts, respectively. Only checked in debug builds, since by the time this is
+    /// detected the (now malformed) length header has already been written.
+    LengthMismatch(u32, u32),
     /// Catchall for syntax error messages.
     Syntax(String),
 }
@@ -45,6 +64,7 @@ impl error::Error for Error {
             Error::UnknownLength => None,
             Error::InvalidDataModel(_) => None,
             Error::DepthLimitExceeded => None,
+            Error::LengthMismatch(..) => None,
             Error::Syntax(..) => None,
         }
     }
@@ -60,6 +80,11 @@ impl Display for Error {
             }
             Error::InvalidDataModel(r) => write!(f, "serialize data model is invalid: {}", r),
             Error::DepthLimitExceeded => f.write_str("depth limit exceeded"),
+            Error::LengthMismatch(declared, actual) => write!(
+                f,
+                "serialize impl declared a length of {} but serialized {} elements",
+                declared, actual
+            ),
             Error::Syntax(ref msg) => f.write_str(msg),
         }
     }
@@ -116,6 +141,32 @@ pub struct Serializer<W, C = DefaultConfig> {
     wr: W,
     config: C,
     depth: usize,
+    /// Struct field name -> index table used by [`Self::with_string_interning`]. `None` unless
+    /// that option is enabled, in which case it is populated lazily as field names are first
+    /// encountered.
+    intern: Option<HashMap<&'static str, u8>>,
+    /// Whether `()` is written as a 0-element array rather than `nil`. See
+    /// [`Self::with_unit_as_empty_array`].
+    unit_as_empty_array: bool,
+    /// Whether byte slices are written using the `str` marker family instead of `bin`. See
+    /// [`Self::with_v4_compat`].
+    v4_compat: bool,
+    /// Whether all NaN `f32`/`f64` values are normalized to a single bit pattern before being
+    /// written. See [`Self::with_canonical_nan`].
+    canonical_nan: bool,
+    /// Whether a map key that serializes to `nil` or a float is rejected as an error rather than
+    /// written as-is. See [`Self::with_error_on_lossy_key`].
+    error_on_lossy_key: bool,
+    /// Whether a zero-length tuple/tuple-struct is written as `nil` rather than a 0-element
+    /// array. See [`Self::with_empty_tuple_as_nil`].
+    empty_tuple_as_nil: bool,
+    /// Whether tuples and tuple-structs are written as maps with integer keys `0..n` instead
+    /// of plain arrays. See [`Self::with_tuple_as_map`].
+    tuple_as_map: bool,
+    /// Whether `std::time::Duration` is written as the compact ext encoding read back by
+    /// [`crate::decode::Deserializer::with_ext_durations`], instead of its default 2-field
+    /// struct representation. See [`Self::with_ext_durations`].
+    ext_durations: bool,
 }
 
 impl<W: Write, C> Serializer<W, C> {
@@ -139,14 +190,16 @@ impl<W: Write, C> Serializer<W, C> {
         self.wr
     }
 
-    /// Changes the maximum nesting depth that is allowed.
+    /// Flushes the underlying writer.
     ///
-    /// Currently unused.
-    #[doc(hidden)]
+    /// Buffered writers such as [`std::io::BufWriter`] don't guarantee that data reaches its
+    /// destination until flushed, so callers that serialize directly with a `Serializer` (rather
+    /// than through [`write`], which flushes for you) should call this once they're done.
     #[inline]
-    pub fn unstable_set_max_depth(&mut self, depth: usize) {
-        self.depth = depth;
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.wr.flush().map_err(|err| Error::InvalidValueWrite(ValueWriteError::InvalidMarkerWrite(err)))
     }
+
 }
 
 impl<W: Write> Serializer<W, DefaultConfig> {
@@ -163,14 +216,41 @@ impl<W: Write> Serializer<W, DefaultConfig> {
             wr,
             depth: 1024,
             config: DefaultConfig,
+            intern: None,
+            unit_as_empty_array: false,
+            v4_compat: false,
+            canonical_nan: false,
+            error_on_lossy_key: false,
+            empty_tuple_as_nil: false,
+            tuple_as_map: false,
+            ext_durations: false,
+        }
+    }
+}
+
+impl<W: Write, C> Serializer<W, C> {
+    /// Decrements the nesting-depth counter, used whenever a new sequence, map, tuple, or struct
+    /// is entered, erroring once the budget set by [`Self::with_max_depth`] is already exhausted
+    /// rather than let the recursion run away and exhaust the stack. `depth` counts down the
+    /// remaining budget, so a fresh `Serializer` with `with_max_depth(N)` allows exactly `N`
+    /// successful calls (i.e. `N` levels of nesting) before the `N + 1`th errors. The counter is
+    /// restored by the matching increment in [`Compound`]'s and [`MaybeUnknownLengthCompound`]'s
+    /// `end`/`finish`.
+    #[inline]
+    fn enter_compound(&mut self) -> Result<(), Error> {
+        if self.depth == 0 {
+            return Err(Error::DepthLimitExceeded);
         }
+        self.depth -= 1;
+        Ok(())
     }
 }
 
 impl<'a, W: Write + 'a, C> Serializer<W, C> {
     #[inline]
     fn compound(&'a mut self) -> Result<Compound<'a, W, C>, Error> {
-        let c = Compound { se: self };
+        self.enter_compound()?;
+        let c = Compound { se: self, sorted_fields: None, tuple_index: None, duration_ext: None };
         Ok(c)
     }
 }
@@ -180,6 +260,7 @@ impl<'a, W: Write + 'a, C: SerializerConfig> Serializer<W, C> {
     fn maybe_unknown_len_compound<F>(&'a mut self, len: Option<usize>, f: F) -> Result<MaybeUnknownLengthCompound<'a, W, C>, Error>
     where F: Fn(&mut W, u32) -> Result<Marker, ValueWriteError>
     {
+        self.enter_compound()?;
         Ok(MaybeUnknownLengthCompound {
             compound: match len {
                 Some(len) => {
@@ -188,6 +269,8 @@ impl<'a, W: Write + 'a, C: SerializerConfig> Serializer<W, C> {
                 }
                 None => Some(UnknownLengthCompound::from(&*self)),
             },
+            declared_len: len.map(|len| len as u32),
+            emitted: 0,
             se: self,
         })
     }
@@ -200,11 +283,43 @@ impl<W: Write, C> Serializer<W, C> {
     /// requirements.
     #[inline]
     pub fn with_struct_map(self) -> Serializer<W, StructMapConfig<C>> {
-        let Serializer { wr, depth, config } = self;
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations } = self;
         Serializer {
             wr,
             depth,
             config: StructMapConfig::new(config),
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will serialize structs as a map
+    /// whose field names are written in lexicographically sorted order rather than declaration
+    /// order.
+    ///
+    /// This is useful when a canonical, order-independent encoding is required, e.g. for hashing
+    /// or diffing.
+    #[inline]
+    pub fn with_struct_map_sorted(self) -> Serializer<W, StructMapSortedConfig<C>> {
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations } = self;
+        Serializer {
+            wr,
+            depth,
+            config: StructMapSortedConfig::new(config),
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
         }
     }
 
@@ -215,11 +330,19 @@ impl<W: Write, C> Serializer<W, C> {
     /// representation.
     #[inline]
     pub fn with_struct_tuple(self) -> Serializer<W, StructTupleConfig<C>> {
-        let Serializer { wr, depth, config } = self;
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations } = self;
         Serializer {
             wr,
             depth,
             config: StructTupleConfig::new(config),
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
         }
     }
 
@@ -232,11 +355,19 @@ impl<W: Write, C> Serializer<W, C> {
     /// versions of `rmp-serde`.
     #[inline]
     pub fn with_human_readable(self) -> Serializer<W, HumanReadableConfig<C>> {
-        let Serializer { wr, depth, config } = self;
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations } = self;
         Serializer {
             wr,
             depth,
             config: HumanReadableConfig::new(config),
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
         }
     }
 
@@ -247,11 +378,278 @@ impl<W: Write, C> Serializer<W, C> {
     /// representation.
     #[inline]
     pub fn with_binary(self) -> Serializer<W, BinaryConfig<C>> {
-        let Serializer { wr, depth, config } = self;
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations } = self;
         Serializer {
             wr,
             depth,
             config: BinaryConfig::new(config),
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will serialize structs as a map
+    /// whose field names are deduplicated via a non-standard string interning extension: each
+    /// distinct field name is written out in full the first time it is seen and referenced by a
+    /// compact index thereafter.
+    ///
+    /// This only pays off when serializing many records that share a schema through the same
+    /// `Serializer`, e.g. a `Vec` of identical-shaped structs serialized in one call; it does not
+    /// help a single, standalone struct. The resulting MessagePack can only be read back by a
+    /// `Deserializer` with the matching `with_string_interning` option enabled.
+    #[inline]
+    pub fn with_string_interning(self) -> Serializer<W, StringInterningConfig<C>> {
+        let Serializer { wr, depth, config, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config: StringInterningConfig::new(config),
+            intern: Some(HashMap::new()),
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will serialize externally-tagged
+    /// enum variants as their declaration-order index instead of their name.
+    ///
+    /// This roughly halves the per-variant overhead for enums with long variant names. No
+    /// matching `Deserializer` option is needed: a variant index is resolved back to its name
+    /// the same generic way `#[derive(Deserialize)]` already resolves integer struct field keys,
+    /// so the reader only needs to agree on variant declaration order.
+    #[inline]
+    pub fn with_integer_variants(self) -> Serializer<W, IntegerVariantConfig<C>> {
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations } = self;
+        Serializer {
+            wr,
+            depth,
+            config: IntegerVariantConfig::new(config),
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will serialize `()` as a 0-element
+    /// array rather than `nil`.
+    ///
+    /// This accommodates schemas that model the unit type as an empty array/tuple rather than a
+    /// null value; unit structs already serialize this way regardless of this option (see
+    /// [`serde::Serializer::serialize_unit_struct`]'s implementation below).
+    #[inline]
+    pub fn with_unit_as_empty_array(self) -> Serializer<W, C> {
+        let Serializer { wr, depth, config, intern, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config,
+            intern,
+            unit_as_empty_array: true,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will serialize byte slices (and
+    /// `serde_bytes` byte buffers) using the `str` marker family instead of `bin`.
+    ///
+    /// The pre-2013 MessagePack spec had no `bin` type: raw byte data shared the same wire
+    /// representation as strings. This accommodates decoders that still only understand that
+    /// spec and would otherwise reject a `bin8`/`bin16`/`bin32` marker outright. Readers that
+    /// understand the current spec keep working unchanged, since a `str`-marker payload is a
+    /// valid (if unusual) way to decode into a byte buffer.
+    #[inline]
+    pub fn with_v4_compat(self) -> Serializer<W, C> {
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config,
+            intern,
+            unit_as_empty_array,
+            v4_compat: true,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will normalize all NaN `f32`/`f64`
+    /// values to a single canonical bit pattern before writing them.
+    ///
+    /// Different NaN bit patterns (e.g. a signaling NaN vs. a quiet NaN, or NaNs with different
+    /// payload bits) all compare unequal to themselves under IEEE 754, but they serialize to
+    /// different bytes by default, which breaks content-addressed schemes that need identical
+    /// values to hash the same. With this option, every NaN of a given width is written using
+    /// that width's canonical bit pattern, while all other values (including infinities and
+    /// negative zero) pass through unchanged.
+    #[inline]
+    pub fn with_canonical_nan(self) -> Serializer<W, C> {
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config,
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan: true,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will error with
+    /// [`Error::InvalidDataModel`] if a map key serializes to `nil` or a float, instead of
+    /// writing it through as-is.
+    ///
+    /// Some MessagePack consumers only accept string or integer map keys and choke on (or
+    /// silently misbehave with) other key types, so this catches the mismatch at serialization
+    /// time rather than leaving it to be discovered on the reading end.
+    #[inline]
+    pub fn with_error_on_lossy_key(self) -> Serializer<W, C> {
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, empty_tuple_as_nil, tuple_as_map, ext_durations, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config,
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key: true,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will write a zero-length tuple or
+    /// tuple-struct as `nil` instead of a 0-element array.
+    ///
+    /// Some cross-language schemas represent "no fields" as null rather than an empty sequence;
+    /// this makes it possible to match that convention. Pair with
+    /// [`crate::decode::Deserializer::with_nil_as_empty_collection`] on the reading end so the
+    /// resulting `nil` is accepted back as an empty tuple/tuple-struct.
+    #[inline]
+    pub fn with_empty_tuple_as_nil(self) -> Serializer<W, C> {
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, tuple_as_map, ext_durations, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config,
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil: true,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will serialize tuples and
+    /// tuple-structs as maps with integer keys `0..n` instead of plain arrays.
+    ///
+    /// This bridges to consumers that index tuple fields by position-as-key rather than by
+    /// array position. Pair with [`crate::decode::Deserializer::with_tuple_as_map`] on the
+    /// reading end so the resulting integer-keyed map is accepted back as a tuple/tuple-struct.
+    #[inline]
+    pub fn with_tuple_as_map(self) -> Serializer<W, C> {
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, ext_durations, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config,
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map: true,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, with the maximum nesting depth it will
+    /// serialize changed to `depth`.
+    ///
+    /// A deeply nested `Serialize` impl (e.g. a recursive data structure, or a pathologically
+    /// nested `Vec<Vec<Vec<...>>>`) recurses once per level through `serialize_seq` /
+    /// `serialize_map` / `serialize_struct` and their tuple/variant counterparts; past this
+    /// limit, [`Error::DepthLimitExceeded`] is returned instead of exhausting the stack.
+    /// Defaults to 1024, matching
+    /// [`Deserializer::set_max_depth`](crate::decode::Deserializer::set_max_depth)'s default on
+    /// the reading end.
+    #[inline]
+    pub fn with_max_depth(self, depth: usize) -> Serializer<W, C> {
+        let Serializer { wr, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, ext_durations, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config,
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations,
+        }
+    }
+
+    /// Consumes this serializer returning the new one, which will write `std::time::Duration` as
+    /// a compact ext type (an 8-byte seconds count followed by a 4-byte nanoseconds count)
+    /// instead of serde's default 2-field struct representation.
+    ///
+    /// `Duration`'s `Serialize` impl lives upstream in `serde` itself, so it cannot be
+    /// special-cased by type the way [`crate::Timestamp`] is; instead this is recognized by
+    /// sniffing [`serde::Serializer::serialize_struct`]'s `name` and `len` arguments for the
+    /// shape `Duration`'s impl is known to produce. The resulting ext can only be read back by a
+    /// [`crate::decode::Deserializer`] with the matching
+    /// [`with_ext_durations`](crate::decode::Deserializer::with_ext_durations) option enabled.
+    #[inline]
+    pub fn with_ext_durations(self) -> Serializer<W, C> {
+        let Serializer { wr, depth, config, intern, unit_as_empty_array, v4_compat, canonical_nan, error_on_lossy_key, empty_tuple_as_nil, tuple_as_map, .. } = self;
+        Serializer {
+            wr,
+            depth,
+            config,
+            intern,
+            unit_as_empty_array,
+            v4_compat,
+            canonical_nan,
+            error_on_lossy_key,
+            empty_tuple_as_nil,
+            tuple_as_map,
+            ext_durations: true,
         }
     }
 }
@@ -279,6 +677,185 @@ impl<W: Write, C> UnderlyingWrite for Serializer<W, C> {
 #[derive(Debug)]
 pub struct Compound<'a, W: 'a, C: 'a> {
     se: &'a mut Serializer<W, C>,
+    /// Buffered `(field name, serialized value)` pairs, used only when the struct's config
+    /// requires fields to be sorted lexicographically before being written.
+    sorted_fields: Option<Vec<(&'static str, Vec<u8>)>>,
+    /// Next integer key to write before an element's value, used only when a tuple or
+    /// tuple-struct is being written as a map. See [`Serializer::with_tuple_as_map`].
+    tuple_index: Option<u32>,
+    /// `Some` while buffering a `std::time::Duration`'s `secs`/`nanos` fields for the
+    /// [`Serializer::with_ext_durations`] ext encoding, built up as `serialize_field` sees each
+    /// (in whichever order `serde`'s own `Duration::serialize` calls them) and flushed into the
+    /// ext payload by `end`. `None` for an ordinary struct.
+    duration_ext: Option<DurationExtFields>,
+}
+
+/// The `secs`/`nanos` values captured so far out of a `std::time::Duration`'s fields by
+/// [`Compound::serialize_field`]. See [`Compound::duration_ext`].
+#[derive(Debug, Default)]
+struct DurationExtFields {
+    secs: Option<u64>,
+    nanos: Option<u32>,
+}
+
+/// Minimal capturing [`serde::Serializer`] used to pull a raw `u64` or `u32` out of a
+/// `std::time::Duration`'s `secs`/`nanos` field. `serde`'s own `Serialize` impl for `Duration`
+/// always calls `serialize_u64` for `secs` and `serialize_u32` for `nanos` and nothing else, so
+/// this only needs to accept those two. Modeled on [`ExtFieldSerializer`]'s narrow-shape-only
+/// pattern.
+#[derive(Debug)]
+struct DurationFieldSerializer;
+
+impl serde::Serializer for DurationFieldSerializer {
+    type Ok = u64;
+    type Error = Error;
+
+    type SerializeSeq = serde::ser::Impossible<u64, Error>;
+    type SerializeTuple = serde::ser::Impossible<u64, Error>;
+    type SerializeTupleStruct = serde::ser::Impossible<u64, Error>;
+    type SerializeTupleVariant = serde::ser::Impossible<u64, Error>;
+    type SerializeMap = serde::ser::Impossible<u64, Error>;
+    type SerializeStruct = serde::ser::Impossible<u64, Error>;
+    type SerializeStructVariant = serde::ser::Impossible<u64, Error>;
+
+    #[inline]
+    fn serialize_u64(self, val: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(val)
+    }
+
+    #[inline]
+    fn serialize_u32(self, val: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(u64::from(val))
+    }
+
+    #[inline]
+    fn serialize_bool(self, _val: bool) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, bool unexpected"))
+    }
+
+    #[inline]
+    fn serialize_i8(self, _val: i8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, i8 unexpected"))
+    }
+
+    #[inline]
+    fn serialize_i16(self, _val: i16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, i16 unexpected"))
+    }
+
+    #[inline]
+    fn serialize_i32(self, _val: i32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, i32 unexpected"))
+    }
+
+    #[inline]
+    fn serialize_i64(self, _val: i64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, i64 unexpected"))
+    }
+
+    #[inline]
+    fn serialize_u8(self, _val: u8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, u8 unexpected"))
+    }
+
+    #[inline]
+    fn serialize_u16(self, _val: u16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, u16 unexpected"))
+    }
+
+    #[inline]
+    fn serialize_f32(self, _val: f32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, f32 unexpected"))
+    }
+
+    #[inline]
+    fn serialize_f64(self, _val: f64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, f64 unexpected"))
+    }
+
+    #[inline]
+    fn serialize_char(self, _val: char) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, char unexpected"))
+    }
+
+    #[inline]
+    fn serialize_str(self, _val: &str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, str unexpected"))
+    }
+
+    #[inline]
+    fn serialize_bytes(self, _val: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, bytes unexpected"))
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, unit unexpected"))
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, unit struct unexpected"))
+    }
+
+    #[inline]
+    fn serialize_unit_variant(self, _name: &'static str, _idx: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, unit variant unexpected"))
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
+        where T: Serialize
+    {
+        Err(Error::InvalidDataModel("expected u64 or u32, newtype struct unexpected"))
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: ?Sized>(self, _name: &'static str, _idx: u32, _variant: &'static str, _value: &T) -> Result<Self::Ok, Self::Error>
+        where T: Serialize
+    {
+        Err(Error::InvalidDataModel("expected u64 or u32, newtype variant unexpected"))
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, none unexpected"))
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+        where T: Serialize
+    {
+        Err(Error::InvalidDataModel("expected u64 or u32, some unexpected"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, seq unexpected"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, tuple unexpected"))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, tuple struct unexpected"))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _idx: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, tuple variant unexpected"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, map unexpected"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, struct unexpected"))
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _idx: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::InvalidDataModel("expected u64 or u32, struct variant unexpected"))
+    }
 }
 
 #[derive(Debug)]
@@ -307,6 +884,7 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeSeq for Compound<'a, W, C>
 
     #[inline(always)]
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.se.depth += 1;
         Ok(())
     }
 }
@@ -317,11 +895,16 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeTuple for Compound<'a, W,
 
     #[inline]
     fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        if let Some(idx) = self.tuple_index.as_mut() {
+            (*idx).serialize(&mut *self.se)?;
+            *idx += 1;
+        }
         value.serialize(&mut *self.se)
     }
 
     #[inline(always)]
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.se.depth += 1;
         Ok(())
     }
 }
@@ -332,11 +915,16 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeTupleStruct for Compound<'
 
     #[inline]
     fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        if let Some(idx) = self.tuple_index.as_mut() {
+            (*idx).serialize(&mut *self.se)?;
+            *idx += 1;
+        }
         value.serialize(&mut *self.se)
     }
 
     #[inline(always)]
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.se.depth += 1;
         Ok(())
     }
 }
@@ -345,19 +933,118 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeStruct for Compound<'a, W,
     type Ok = ();
     type Error = Error;
 
-    #[inline]
     fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) ->
         Result<(), Self::Error>
     {
-        C::write_struct_field(&mut *self.se, key, value)
+        if let Some(fields) = self.duration_ext.as_mut() {
+            let captured = value.serialize(DurationFieldSerializer)?;
+            return match key {
+                "secs" => {
+                    fields.secs = Some(captured);
+                    Ok(())
+                }
+                "nanos" => {
+                    fields.nanos = Some(captured as u32);
+                    Ok(())
+                }
+                _ => Err(Error::InvalidDataModel("expected Duration field `secs` or `nanos`, found an unexpected field")),
+            };
+        }
+
+        match self.sorted_fields.as_mut() {
+            None if self.se.intern.is_some() => write_interned_struct_field(self.se, key, value),
+            None => C::write_struct_field(&mut *self.se, key, value),
+            Some(fields) => {
+                let mut buf = Vec::new();
+                // As with `UnknownLengthCompound`, this scratch sub-serializer does not share the
+                // parent's interning table: the field name it writes is not visible here, only
+                // the pre-encoded value bytes it produces.
+                let mut sub = Serializer {
+                    wr: &mut buf,
+                    config: self.se.config,
+                    depth: self.se.depth,
+                    intern: None,
+                    unit_as_empty_array: self.se.unit_as_empty_array,
+                    v4_compat: self.se.v4_compat,
+                    canonical_nan: self.se.canonical_nan,
+                    error_on_lossy_key: self.se.error_on_lossy_key,
+                    empty_tuple_as_nil: self.se.empty_tuple_as_nil,
+                    tuple_as_map: self.se.tuple_as_map,
+                    ext_durations: self.se.ext_durations,
+                };
+                value.serialize(&mut sub)?;
+                fields.push((key, buf));
+                Ok(())
+            }
+        }
     }
 
-    #[inline(always)]
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        if let Some(fields) = self.duration_ext {
+            let secs = fields.secs
+                .ok_or(Error::InvalidDataModel("Duration ext encoding is missing its `secs` field"))?;
+            let nanos = fields.nanos
+                .ok_or(Error::InvalidDataModel("Duration ext encoding is missing its `nanos` field"))?;
+
+            let mut payload = [0u8; 12];
+            payload[..8].copy_from_slice(&secs.to_be_bytes());
+            payload[8..].copy_from_slice(&nanos.to_be_bytes());
+            encode::write_ext_meta(&mut self.se.wr, payload.len() as u32, DURATION_EXT_TYPE)?;
+            self.se.wr.write_all(&payload).map_err(ValueWriteError::InvalidDataWrite)?;
+
+            self.se.depth += 1;
+            return Ok(());
+        }
+
+        if let Some(mut fields) = self.sorted_fields {
+            fields.sort_by_key(|(key, _)| *key);
+            encode::write_map_len(&mut self.se.wr, fields.len() as u32)?;
+            for (key, buf) in fields {
+                encode::write_str(&mut self.se.wr, key)?;
+                self.se.wr.write_all(&buf).map_err(ValueWriteError::InvalidDataWrite)?;
+            }
+        }
+        self.se.depth += 1;
         Ok(())
     }
 }
 
+/// Writes a struct field name using the interning table on `se`, consulting or extending it as
+/// needed, then serializes the value. See [`Serializer::with_string_interning`].
+fn write_interned_struct_field<W, C, T>(se: &mut Serializer<W, C>, key: &'static str, value: &T) -> Result<(), Error>
+where
+    W: Write,
+    C: SerializerConfig,
+    T: ?Sized + Serialize,
+{
+    let cached_idx = {
+        let table = se.intern.get_or_insert_with(HashMap::new);
+        match table.get(key).copied() {
+            found @ Some(_) => found,
+            None => {
+                let idx = table.len();
+                if idx > u8::MAX as usize {
+                    return Err(Error::InvalidDataModel(
+                        "too many distinct struct field names for string interning (max 256)",
+                    ));
+                }
+                table.insert(key, idx as u8);
+                None
+            }
+        }
+    };
+    match cached_idx {
+        Some(idx) => {
+            encode::write_ext_meta(&mut se.wr, 1, STRING_INTERN_EXT_TYPE)?;
+            se.wr
+                .write_all(&[idx])
+                .map_err(|err| Error::InvalidValueWrite(ValueWriteError::InvalidDataWrite(err)))?;
+        }
+        None => encode::write_str(&mut se.wr, key)?,
+    }
+    value.serialize(&mut *se)
+}
+
 impl<'a, W: Write + 'a, C: SerializerConfig> SerializeTupleVariant for Compound<'a, W, C> {
     type Ok = ();
     type Error = Error;
@@ -369,6 +1056,7 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeTupleVariant for Compound<
 
     #[inline(always)]
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.se.depth += 1;
         Ok(())
     }
 }
@@ -380,12 +1068,11 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeStructVariant for Compound
     fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) ->
         Result<(), Self::Error>
     {
-        C::write_struct_field(&mut *self.se, key, value)
+        <Self as SerializeStruct>::serialize_field(self, key, value)
     }
 
-    #[inline(always)]
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(())
+        <Self as SerializeStruct>::end(self)
     }
 }
 
@@ -399,7 +1086,23 @@ struct UnknownLengthCompound<C> {
 impl<W, C: SerializerConfig> From<&Serializer<W, C>> for UnknownLengthCompound<C> {
     fn from(se: &Serializer<W, C>) -> Self {
         Self {
-            se: Serializer { wr: Vec::with_capacity(128), config: se.config, depth: se.depth },
+            // Struct field names encoded inside this scratch buffer are written out in full,
+            // rather than sharing the parent `Serializer`'s interning table: they end up copied
+            // into the parent's output as an opaque byte blob, with no opportunity to consult or
+            // update that table from here.
+            se: Serializer {
+                wr: Vec::with_capacity(128),
+                config: se.config,
+                depth: se.depth,
+                intern: None,
+                unit_as_empty_array: se.unit_as_empty_array,
+                v4_compat: se.v4_compat,
+                canonical_nan: se.canonical_nan,
+                error_on_lossy_key: se.error_on_lossy_key,
+                empty_tuple_as_nil: se.empty_tuple_as_nil,
+                tuple_as_map: se.tuple_as_map,
+                ext_durations: se.ext_durations,
+            },
             elem_count: 0
         }
     }
@@ -424,6 +1127,43 @@ impl<W, C: SerializerConfig> From<&Serializer<W, C>> for UnknownLengthCompound<C
 pub struct MaybeUnknownLengthCompound<'a, W: 'a, C: 'a> {
     se: &'a mut Serializer<W, C>,
     compound: Option<UnknownLengthCompound<C>>,
+    /// The length passed to `serialize_seq`/`serialize_map`, when it was known up front (i.e.
+    /// `compound` is `None`, since the length header has already been written directly to `se`).
+    /// Used only to debug-assert against `emitted` in `finish`.
+    declared_len: Option<u32>,
+    /// Count of `serialize_element` calls so far, only tracked when `compound` is `None`. For
+    /// maps this counts both keys and values, so it is twice the number of entries.
+    emitted: u32,
+}
+
+impl<'a, W: Write + 'a, C: SerializerConfig> MaybeUnknownLengthCompound<'a, W, C> {
+    /// Shared `end()` logic for both `SerializeSeq` and `SerializeMap`. `divisor` is 2 for maps,
+    /// since each entry reaches `serialize_element` as two separate calls (key and value).
+    fn finish<F>(self, divisor: u32, write_len: F) -> Result<(), Error>
+    where F: FnOnce(&mut W, u32) -> Result<Marker, ValueWriteError>
+    {
+        let res = match self.compound {
+            Some(compound) => {
+                write_len(&mut self.se.wr, compound.elem_count / divisor)?;
+                self.se.wr.write_all(&compound.se.into_inner())
+                    .map_err(ValueWriteError::InvalidDataWrite)?;
+                Ok(())
+            }
+            None => {
+                #[cfg(debug_assertions)]
+                if let Some(declared) = self.declared_len {
+                    let actual = self.emitted / divisor;
+                    if declared != actual {
+                        self.se.depth += 1;
+                        return Err(Error::LengthMismatch(declared, actual));
+                    }
+                }
+                Ok(())
+            }
+        };
+        self.se.depth += 1;
+        res
+    }
 }
 
 impl<'a, W: Write + 'a, C: SerializerConfig> SerializeSeq for MaybeUnknownLengthCompound<'a, W, C> {
@@ -432,7 +1172,11 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeSeq for MaybeUnknownLength
 
     fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
         match self.compound.as_mut() {
-            None => value.serialize(&mut *self.se),
+            None => {
+                value.serialize(&mut *self.se)?;
+                self.emitted += 1;
+                Ok(())
+            }
             Some(buf) => {
                 value.serialize(&mut buf.se)?;
                 buf.elem_count += 1;
@@ -442,12 +1186,7 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeSeq for MaybeUnknownLength
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        if let Some(compound) = self.compound {
-            encode::write_array_len(&mut self.se.wr, compound.elem_count)?;
-            self.se.wr.write_all(&compound.se.into_inner())
-                .map_err(ValueWriteError::InvalidDataWrite)?;
-        }
-        Ok(())
+        self.finish(1, encode::write_array_len)
     }
 }
 
@@ -456,6 +1195,44 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeMap for MaybeUnknownLength
     type Error = Error;
 
     fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        if self.se.error_on_lossy_key {
+            // Serialize into a scratch buffer first so the key's leading marker byte can be
+            // inspected before it's committed to the real output.
+            let mut buf = Vec::new();
+            let mut sub = Serializer {
+                wr: &mut buf,
+                config: self.se.config,
+                depth: self.se.depth,
+                intern: None,
+                unit_as_empty_array: self.se.unit_as_empty_array,
+                v4_compat: self.se.v4_compat,
+                canonical_nan: self.se.canonical_nan,
+                error_on_lossy_key: self.se.error_on_lossy_key,
+                empty_tuple_as_nil: self.se.empty_tuple_as_nil,
+                tuple_as_map: self.se.tuple_as_map,
+                ext_durations: self.se.ext_durations,
+            };
+            key.serialize(&mut sub)?;
+            if let Some(&marker_byte) = buf.first() {
+                if matches!(Marker::from_u8(marker_byte), Marker::Null | Marker::F32 | Marker::F64) {
+                    return Err(Error::InvalidDataModel(
+                        "map key serializes to nil or a float, rejected by with_error_on_lossy_key",
+                    ));
+                }
+            }
+            return match self.compound.as_mut() {
+                None => {
+                    self.se.wr.write_all(&buf).map_err(|err| Error::InvalidValueWrite(ValueWriteError::InvalidDataWrite(err)))?;
+                    self.emitted += 1;
+                    Ok(())
+                }
+                Some(compound) => {
+                    compound.se.wr.write_all(&buf).map_err(|err| Error::InvalidValueWrite(ValueWriteError::InvalidDataWrite(err)))?;
+                    compound.elem_count += 1;
+                    Ok(())
+                }
+            };
+        }
         <Self as SerializeSeq>::serialize_element(self, key)
     }
 
@@ -464,12 +1241,7 @@ impl<'a, W: Write + 'a, C: SerializerConfig> SerializeMap for MaybeUnknownLength
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        if let Some(compound) = self.compound {
-            encode::write_map_len(&mut self.se.wr, compound.elem_count / 2)?;
-            self.se.wr.write_all(&compound.se.into_inner())
-                .map_err(ValueWriteError::InvalidDataWrite)?;
-        }
-        Ok(())
+        self.finish(2, encode::write_map_len)
     }
 }
 
@@ -516,6 +1288,9 @@ where
     }
 
     fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        if self.is_human_readable() {
+            return self.serialize_str(&v.to_string());
+        }
         self.serialize_bytes(&v.to_be_bytes())
     }
 
@@ -537,15 +1312,20 @@ where
     }
 
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        if self.is_human_readable() {
+            return self.serialize_str(&v.to_string());
+        }
         self.serialize_bytes(&v.to_be_bytes())
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        let v = if self.canonical_nan && v.is_nan() { f32::NAN } else { v };
         encode::write_f32(&mut self.wr, v)?;
         Ok(())
     }
 
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        let v = if self.canonical_nan && v.is_nan() { f64::NAN } else { v };
         encode::write_f64(&mut self.wr, v)?;
         Ok(())
     }
@@ -562,7 +1342,11 @@ where
     }
 
     fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
-        encode::write_bin_len(&mut self.wr, value.len() as u32)?;
+        if self.v4_compat {
+            encode::write_str_len(&mut self.wr, value.len() as u32)?;
+        } else {
+            encode::write_bin_len(&mut self.wr, value.len() as u32)?;
+        }
         self.wr
             .write_all(value)
             .map_err(|err| Error::InvalidValueWrite(ValueWriteError::InvalidDataWrite(err)))
@@ -577,6 +1361,10 @@ where
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        if self.unit_as_empty_array {
+            encode::write_array_len(&mut self.wr, 0)?;
+            return Ok(());
+        }
         encode::write_nil(&mut self.wr)
             .map_err(|err| Error::InvalidValueWrite(ValueWriteError::InvalidMarkerWrite(err)))
     }
@@ -617,7 +1405,18 @@ where
 
     //TODO: normal compund
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        encode::write_array_len(&mut self.wr, len as u32)?;
+        if self.tuple_as_map {
+            encode::write_map_len(&mut self.wr, len as u32)?;
+            self.enter_compound()?;
+            return Ok(Compound { se: self, sorted_fields: None, tuple_index: Some(0), duration_ext: None });
+        }
+
+        if len == 0 && self.empty_tuple_as_nil {
+            encode::write_nil(&mut self.wr)
+                .map_err(|err| Error::InvalidValueWrite(ValueWriteError::InvalidMarkerWrite(err)))?;
+        } else {
+            encode::write_array_len(&mut self.wr, len as u32)?;
+        }
 
         self.compound()
     }
@@ -625,7 +1424,18 @@ where
     fn serialize_tuple_struct(self, _name: &'static str, len: usize) ->
         Result<Self::SerializeTupleStruct, Self::Error>
     {
-        encode::write_array_len(&mut self.wr, len as u32)?;
+        if self.tuple_as_map {
+            encode::write_map_len(&mut self.wr, len as u32)?;
+            self.enter_compound()?;
+            return Ok(Compound { se: self, sorted_fields: None, tuple_index: Some(0), duration_ext: None });
+        }
+
+        if len == 0 && self.empty_tuple_as_nil {
+            encode::write_nil(&mut self.wr)
+                .map_err(|err| Error::InvalidValueWrite(ValueWriteError::InvalidMarkerWrite(err)))?;
+        } else {
+            encode::write_array_len(&mut self.wr, len as u32)?;
+        }
 
         self.compound()
     }
@@ -643,11 +1453,26 @@ where
         self.maybe_unknown_len_compound(len, |wr, len| encode::write_map_len(wr, len))
     }
 
-    fn serialize_struct(self, _name: &'static str, len: usize) ->
+    fn serialize_struct(self, name: &'static str, len: usize) ->
         Result<Self::SerializeStruct, Self::Error>
     {
-        C::write_struct_len(self, len)?;
-        self.compound()
+        if self.ext_durations && name == "Duration" && len == 2 {
+            self.enter_compound()?;
+            // `Duration`'s `Serialize` impl is `serde`'s own, so it can't be special-cased by
+            // type like `crate::Timestamp` is; its two fields are buffered here and turned into
+            // the ext payload once both have been seen, in `SerializeStruct::end`.
+            return Ok(Compound { se: self, sorted_fields: None, tuple_index: None, duration_ext: Some(DurationExtFields::default()) });
+        }
+
+        if C::sorts_struct_fields() {
+            self.enter_compound()?;
+            // The map length header is written once the sorted field order is known, in
+            // `SerializeStruct::end`.
+            Ok(Compound { se: self, sorted_fields: Some(Vec::with_capacity(len)), tuple_index: None, duration_ext: None })
+        } else {
+            C::write_struct_len(self, len)?;
+            self.compound()
+        }
     }
 
     fn serialize_struct_variant(self, name: &'static str, id: u32, variant: &'static str, len: usize) ->
@@ -1042,6 +1867,61 @@ impl<'a, W: Write + 'a> ExtFieldSerializer<'a, W> {
     }
 }
 
+/// A [`Write`] implementor that discards every byte written to it, tracking only how many bytes
+/// passed through.
+///
+/// Wrapping this in a [`Serializer`] gives the encoded size of a value without allocating a
+/// buffer to hold it, which is handy for framing protocols that need to know a payload's length
+/// up front.
+#[derive(Clone, Debug, Default)]
+pub struct CountingWrite {
+    count: u64,
+}
+
+impl CountingWrite {
+    /// Constructs a new `CountingWrite` starting from a count of zero.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bytes written so far.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl Write for CountingWrite {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.count += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Returns the number of bytes [`write`] would write for `val`, without allocating a buffer to
+/// hold them.
+///
+/// This serializes `val` a full time against a [`CountingWrite`], so computing it before encoding
+/// `val` for real (as the `precompute-size` feature's [`to_vec`] fast path does) means traversing
+/// the value twice. Prefer this only when the upfront size is worth more than that second
+/// traversal, e.g. to presize a buffer shared across many calls or to report a payload's length
+/// before it's written.
+pub fn serialized_size<T>(val: &T) -> Result<u64, Error>
+where
+    T: Serialize + ?Sized
+{
+    let mut se = Serializer::new(CountingWrite::new());
+    val.serialize(&mut se)?;
+    Ok(se.into_inner().count())
+}
+
 /// Serialize the given data structure as MessagePack into the I/O stream.
 /// This function uses compact representation - structures as arrays
 ///
@@ -1052,7 +1932,9 @@ where
     W: Write + ?Sized,
     T: Serialize + ?Sized
 {
-    val.serialize(&mut Serializer::new(wr))
+    let mut se = Serializer::new(wr);
+    val.serialize(&mut se)?;
+    se.flush()
 }
 
 /// Serialize the given data structure as MessagePack into the I/O stream.
@@ -1065,14 +1947,24 @@ where
     T: Serialize + ?Sized
 {
     let mut se = Serializer::new(wr).with_struct_map();
-    val.serialize(&mut se)
+    val.serialize(&mut se)?;
+    se.flush()
 }
 
 /// Serialize the given data structure as a MessagePack byte vector.
 /// This method uses compact representation, structs are serialized as arrays
 ///
 /// Serialization can fail if `T`'s implementation of `Serialize` decides to fail.
+///
+/// Without the `precompute-size` feature, the returned buffer starts with a fixed 128-byte
+/// capacity, so large values may reallocate as they grow. With `precompute-size` enabled, `val`
+/// is serialized twice: once against a [`CountingWrite`] to learn the exact size via
+/// [`serialized_size`], then again into a `Vec` preallocated to that size, guaranteeing a single
+/// allocation at the cost of the extra traversal. That trade-off is worth it for large values
+/// serialized once, and not worth it for small values serialized often — the feature is off by
+/// default for that reason.
 #[inline]
+#[cfg(not(feature = "precompute-size"))]
 pub fn to_vec<T>(val: &T) -> Result<Vec<u8>, Error>
 where
     T: Serialize + ?Sized
@@ -1082,6 +1974,27 @@ where
     Ok(wr)
 }
 
+/// Serialize the given data structure as a MessagePack byte vector.
+/// This method uses compact representation, structs are serialized as arrays
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to fail.
+///
+/// This is the `precompute-size` variant: `val` is serialized twice, once against a
+/// [`CountingWrite`] to learn the exact size via [`serialized_size`], then again into a `Vec`
+/// preallocated to that size, guaranteeing a single allocation at the cost of the extra
+/// traversal. See the non-`precompute-size` doc for when that trade-off pays off.
+#[inline]
+#[cfg(feature = "precompute-size")]
+pub fn to_vec<T>(val: &T) -> Result<Vec<u8>, Error>
+where
+    T: Serialize + ?Sized
+{
+    let size = serialized_size(val)?;
+    let mut wr = Vec::with_capacity(size as usize);
+    write(&mut wr, val)?;
+    Ok(wr)
+}
+
 /// Serializes data structure into byte vector as a map
 /// Resulting MessagePack message will contain field names
 ///
@@ -1097,3 +2010,115 @@ where
     write_named(&mut wr, val)?;
     Ok(wr)
 }
+
+/// Serialize the given data structure as MessagePack into the I/O stream, writing structs as
+/// maps whose field names are sorted lexicographically rather than emitted in declaration order.
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to fail.
+pub fn write_named_sorted<W, T>(wr: &mut W, val: &T) -> Result<(), Error>
+where
+    W: Write + ?Sized,
+    T: Serialize + ?Sized
+{
+    let mut se = Serializer::new(wr).with_struct_map_sorted();
+    val.serialize(&mut se)?;
+    se.flush()
+}
+
+/// Serializes data structure into byte vector as a map whose field names are sorted
+/// lexicographically rather than emitted in declaration order.
+///
+/// This differs from ordinary `HashMap` key sorting in that struct field names are known
+/// statically, so the sort order is deterministic across serializations regardless of the
+/// struct's declaration order.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to fail.
+#[inline]
+pub fn to_vec_named_sorted<T>(val: &T) -> Result<Vec<u8>, Error>
+where
+    T: Serialize + ?Sized
+{
+    let mut wr = Vec::with_capacity(128);
+    write_named_sorted(&mut wr, val)?;
+    Ok(wr)
+}
+
+/// Serializes the given data structure as MessagePack into `buf`, clearing it first rather than
+/// allocating a fresh `Vec` the way [`to_vec`] does. This uses compact representation, structs
+/// are serialized as arrays.
+///
+/// Intended for high-throughput callers that serialize many small messages in a loop and want to
+/// reuse one buffer's allocation across calls instead of paying for a new one every time: reuse
+/// `buf` across calls and read the freshly-written message back out of it before the next call
+/// clears it again.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to fail.
+#[inline]
+pub fn to_vec_reuse<T>(buf: &mut Vec<u8>, val: &T) -> Result<(), Error>
+where
+    T: Serialize + ?Sized
+{
+    buf.clear();
+    write(buf, val)
+}
+
+/// Serializes an [`Ipv4Addr`](std::net::Ipv4Addr) as a 4-byte `bin`, instead of serde's default
+/// 4-element tuple of octets (a fixarray of 4 integers, where each octet above 127 takes an
+/// extra `u8` marker byte).
+///
+/// Pair with [`decode::compact_ipv4addr`](crate::decode::compact_ipv4addr) to read it back:
+///
+/// ```
+/// use std::net::Ipv4Addr;
+///
+/// #[derive(serde_derive::Serialize)]
+/// struct Peer {
+///     #[serde(serialize_with = "rmp_serde::encode::compact_ipv4addr")]
+///     addr: Ipv4Addr,
+/// }
+/// ```
+pub fn compact_ipv4addr<S>(addr: &std::net::Ipv4Addr, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_bytes(&addr.octets())
+}
+
+/// Serializes an [`Ipv6Addr`](std::net::Ipv6Addr) as a 16-byte `bin`, instead of serde's default
+/// 16-element tuple of octets.
+///
+/// Pair with [`decode::compact_ipv6addr`](crate::decode::compact_ipv6addr) to read it back.
+pub fn compact_ipv6addr<S>(addr: &std::net::Ipv6Addr, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_bytes(&addr.octets())
+}
+
+/// Serializes a `SmallVec<[u8; N]>` as a `bin`, instead of smallvec's own `Serialize` impl (which
+/// treats it as a plain seq, one individually-tagged element per byte).
+///
+/// Pair with [`decode::compact_smallvec_bytes`](crate::decode::compact_smallvec_bytes) to read it
+/// back:
+///
+/// ```
+/// use smallvec::SmallVec;
+///
+/// #[derive(serde_derive::Serialize)]
+/// struct Frame {
+///     #[serde(serialize_with = "rmp_serde::encode::compact_smallvec_bytes")]
+///     payload: SmallVec<[u8; 16]>,
+/// }
+/// ```
+#[cfg(feature = "smallvec")]
+pub fn compact_smallvec_bytes<S, A>(val: &smallvec::SmallVec<A>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    A: smallvec::Array<Item = u8>,
+{
+    serializer.serialize_bytes(val)
+}
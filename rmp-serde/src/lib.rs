@@ -61,6 +61,7 @@
 #[macro_use]
 extern crate serde;
 
+use std::convert::TryInto;
 use std::fmt::{self, Display, Formatter};
 use std::str::{self, Utf8Error};
 
@@ -70,13 +71,16 @@ use serde::{Deserialize, Serialize};
 pub use crate::decode::{from_read, Deserializer};
 #[allow(deprecated)]
 pub use crate::decode::from_read_ref;
-pub use crate::encode::{to_vec, to_vec_named, Serializer};
+pub use crate::encode::{to_vec, to_vec_named, to_vec_named_sorted, to_vec_reuse, Serializer};
 
 pub use crate::decode::from_slice;
 
 pub mod config;
+pub mod debug;
 pub mod decode;
 pub mod encode;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 /// Name of Serde newtype struct to Represent Msgpack's Ext
 /// Msgpack Ext: Ext(tag, binary)
@@ -371,3 +375,151 @@ impl<'de> Deserialize<'de> for RawRef<'de> {
         de.deserialize_any(RawRefVisitor)
     }
 }
+
+/// Tag reserved by the MessagePack spec for the `timestamp` extension type.
+const TIMESTAMP_EXT_TYPE: i8 = -1;
+
+/// A MessagePack `timestamp` extension value.
+///
+/// This is a plain, dependency-free representation (no `chrono`, no `std::time::SystemTime`) so
+/// it can be used from `no_std`-leaning consumers as well. It (de)serializes using the ext-type
+/// convention documented on [`MSGPACK_EXT_STRUCT_NAME`], so it round-trips correctly through
+/// [`Serializer`] and [`Deserializer`]. It is a plain Rust struct, but every format sees the
+/// same `(tag, bytes)` ext wrapper rather than `secs`/`nanos` fields.
+///
+/// On encode, the most compact of the three spec-defined representations is chosen: 32-bit
+/// (seconds only, no nanoseconds, fits in `u32`), 64-bit (fits in 34 bits of seconds with
+/// nanoseconds), or 96-bit (everything else, including negative seconds).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    /// Seconds since 1970-01-01T00:00:00Z. May be negative.
+    pub secs: i64,
+    /// Nanoseconds. Must be in the range `0..1_000_000_000`.
+    pub nanos: u32,
+}
+
+impl Timestamp {
+    /// Creates a new `Timestamp` from its components.
+    #[inline]
+    pub fn new(secs: i64, nanos: u32) -> Self {
+        Timestamp { secs, nanos }
+    }
+
+    fn to_ext_bytes(self) -> Vec<u8> {
+        if self.nanos == 0 && self.secs >= 0 && self.secs <= u32::MAX as i64 {
+            (self.secs as u32).to_be_bytes().to_vec()
+        } else if self.secs >= 0 && self.secs < (1 << 34) {
+            let data64 = ((self.nanos as u64) << 34) | self.secs as u64;
+            data64.to_be_bytes().to_vec()
+        } else {
+            let mut buf = Vec::with_capacity(12);
+            buf.extend_from_slice(&self.nanos.to_be_bytes());
+            buf.extend_from_slice(&self.secs.to_be_bytes());
+            buf
+        }
+    }
+
+    fn from_ext_bytes<E: de::Error>(bytes: &[u8]) -> Result<Self, E> {
+        match bytes.len() {
+            4 => {
+                let secs = u32::from_be_bytes(bytes.try_into().unwrap());
+                Ok(Timestamp { secs: secs as i64, nanos: 0 })
+            }
+            8 => {
+                let data64 = u64::from_be_bytes(bytes.try_into().unwrap());
+                Ok(Timestamp {
+                    secs: (data64 & 0x3_ffff_ffff) as i64,
+                    nanos: (data64 >> 34) as u32,
+                })
+            }
+            12 => Ok(Timestamp {
+                nanos: u32::from_be_bytes(bytes[..4].try_into().unwrap()),
+                secs: i64::from_be_bytes(bytes[4..].try_into().unwrap()),
+            }),
+            len => Err(de::Error::custom(format_args!("invalid timestamp extension length: {}", len))),
+        }
+    }
+}
+
+// A plain `Vec<u8>`/`&[u8]` serializes through serde's data model as a seq of individually
+// tagged bytes, not a single msgpack `bin`, so the ext payload needs its own thin wrapper that
+// goes through `serialize_bytes`/`deserialize_bytes` directly, the same way
+// `encode::compact_ipv6addr`/`decode::compact_ipv6addr` do for a field.
+struct ExtBytes<'a>(&'a [u8]);
+
+impl<'a> Serialize for ExtBytes<'a> {
+    fn serialize<S>(&self, se: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer
+    {
+        se.serialize_bytes(self.0)
+    }
+}
+
+struct ExtBytesBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ExtBytesBuf {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+        where D: de::Deserializer<'de>
+    {
+        struct BytesVisitor;
+
+        impl<'de> de::Visitor<'de> for BytesVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, fmt: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+                fmt.write_str("a byte buffer")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(v.to_vec())
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(v)
+            }
+        }
+
+        de.deserialize_bytes(BytesVisitor).map(ExtBytesBuf)
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, se: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer
+    {
+        let bytes = self.to_ext_bytes();
+        let ext = (TIMESTAMP_EXT_TYPE, ExtBytes(&bytes));
+        se.serialize_newtype_struct(MSGPACK_EXT_STRUCT_NAME, &ext)
+    }
+}
+
+struct TimestampVisitor;
+
+impl<'de> de::Visitor<'de> for TimestampVisitor {
+    type Value = Timestamp;
+
+    #[cold]
+    fn expecting(&self, fmt: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        "a MessagePack timestamp extension".fmt(fmt)
+    }
+
+    fn visit_newtype_struct<D>(self, de: D) -> Result<Self::Value, D::Error>
+        where D: de::Deserializer<'de>
+    {
+        let (tag, bytes): (i8, ExtBytesBuf) = Deserialize::deserialize(de)?;
+        if tag != TIMESTAMP_EXT_TYPE {
+            return Err(de::Error::custom(format_args!("expected timestamp ext type {}, found {}", TIMESTAMP_EXT_TYPE, tag)));
+        }
+
+        Timestamp::from_ext_bytes(bytes.0.as_ref())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    #[inline]
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+        where D: de::Deserializer<'de>
+    {
+        de.deserialize_newtype_struct(MSGPACK_EXT_STRUCT_NAME, TimestampVisitor)
+    }
+}
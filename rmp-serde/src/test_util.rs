@@ -0,0 +1,87 @@
+//! Test helpers for downstream crates, gated behind the `test-util` feature.
+//!
+//! This formalizes the roundtrip-checking pattern this crate's own test suite already relies on,
+//! so other crates can validate their own `Serialize`/`Deserialize` impls against each
+//! combination of the human-readable and struct-map settings, rather than just the default one.
+//! Other options (string interning, integer variants and the like) are not exercised.
+
+use serde::{Deserialize, Serialize};
+
+use crate::decode::ReadReader;
+use crate::{Deserializer, Serializer};
+
+/// Serializes and deserializes `val` under every combination of the human-readable and
+/// struct-map settings, asserting that the result equals `val` each time.
+///
+/// This catches types that only round-trip correctly by accident under the default
+/// binary/struct-as-array configuration (a common way to hide a `#[serde(rename)]` or manual
+/// `Deserialize` impl that assumes one particular wire representation).
+///
+/// # Examples
+///
+/// ```
+/// #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+/// enum Direction {
+///     North,
+///     South { degrees: u32 },
+/// }
+///
+/// rmp_serde::test_util::assert_roundtrips(Direction::North);
+/// rmp_serde::test_util::assert_roundtrips(Direction::South { degrees: 180 });
+/// ```
+#[track_caller]
+pub fn assert_roundtrips<T>(val: T)
+where
+    T: PartialEq + std::fmt::Debug + Serialize + for<'de> Deserialize<'de>,
+{
+    assert_roundtrip_config(&val, "default", |s| s, |d| d);
+    assert_roundtrip_config(&val, ".with_struct_map()", |s| s.with_struct_map(), |d| d);
+    assert_roundtrip_config(
+        &val,
+        ".with_human_readable()",
+        |s| s.with_human_readable(),
+        |d| d.with_human_readable(),
+    );
+    assert_roundtrip_config(
+        &val,
+        ".with_human_readable().with_struct_map()",
+        |s| s.with_human_readable().with_struct_map(),
+        |d| d.with_human_readable(),
+    );
+}
+
+#[track_caller]
+fn assert_roundtrip_config<T, CSF, SC, CDF, DC>(
+    val: &T,
+    desc: &str,
+    config_serializer: CSF,
+    config_deserializer: CDF,
+) where
+    T: PartialEq + std::fmt::Debug + Serialize + for<'de> Deserialize<'de>,
+    CSF: FnOnce(Serializer<Vec<u8>>) -> Serializer<Vec<u8>, SC>,
+    SC: crate::config::SerializerConfig,
+    CDF: FnOnce(Deserializer<ReadReader<&[u8]>>) -> Deserializer<ReadReader<&[u8]>, DC>,
+    DC: crate::config::SerializerConfig,
+{
+    let mut serializer = config_serializer(Serializer::new(Vec::new()));
+    if let Err(e) = val.serialize(&mut serializer) {
+        panic!("Failed to serialize: {}\nConfig: {}\nValue: {:?}\n", e, desc, val);
+    }
+    let serialized = serializer.into_inner();
+
+    let mut deserializer = config_deserializer(Deserializer::new(serialized.as_slice()));
+    let val2: T = match T::deserialize(&mut deserializer) {
+        Ok(t) => t,
+        Err(e) => {
+            panic!(
+                "Does not deserialize: {}\nConfig: {}\nValue: {:?}\nSerialized: {}",
+                e,
+                desc,
+                val,
+                crate::debug::annotate(&serialized).unwrap_or_else(|e| format!("<failed to annotate: {}>", e)),
+            );
+        }
+    };
+
+    assert_eq!(val, &val2, "Config: {}", desc);
+}
@@ -0,0 +1,32 @@
+extern crate rmp_serde as rmps;
+
+use rmps::debug::annotate;
+
+#[test]
+fn pass_annotate_small_map() {
+    // {"a": 1, "b": "x"}
+    let buf: Vec<u8> = vec![
+        0x82, // fixmap(2)
+        0xa1, 0x61, // "a"
+        0x01, // fixpos(1)
+        0xa1, 0x62, // "b"
+        0xa1, 0x78, // "x"
+    ];
+
+    let annotated = annotate(&buf).unwrap();
+
+    assert!(annotated.contains("fixmap(2)"), "{}", annotated);
+    assert!(annotated.contains("str1(\"a\")"), "{}", annotated);
+    assert!(annotated.contains("fixpos(1)"), "{}", annotated);
+    assert!(annotated.contains("str1(\"b\")"), "{}", annotated);
+    assert!(annotated.contains("str1(\"x\")"), "{}", annotated);
+}
+
+#[test]
+fn pass_annotate_empty_array() {
+    let buf = [0x90];
+
+    let annotated = annotate(&buf).unwrap();
+
+    assert_eq!("90         fixarray(0)\n", annotated);
+}
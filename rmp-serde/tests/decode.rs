@@ -1,5 +1,6 @@
 extern crate rmp_serde as rmps;
 
+use std::convert::TryInto;
 use std::fmt::{self, Formatter};
 use std::io::Cursor;
 
@@ -255,6 +256,54 @@ fn fail_tuple_len_mismatch() {
     }
 }
 
+#[test]
+fn fail_array_too_long_for_fixed_size() {
+    // msgpack array of 4 elements, decoded into `[u32; 3]`.
+    let buf = [0x94, 0x01, 0x02, 0x03, 0x04];
+    let mut de = Deserializer::new(Cursor::new(&buf[..]));
+
+    let actual: Result<[u32; 3], Error> = Deserialize::deserialize(&mut de);
+    match actual.err().unwrap() {
+        Error::LengthMismatch(3) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn fail_array_too_short_for_fixed_size() {
+    // msgpack array of 3 elements, decoded into `[u32; 4]`.
+    let buf = [0x93, 0x01, 0x02, 0x03];
+    let mut de = Deserializer::new(Cursor::new(&buf[..]));
+
+    let actual: Result<[u32; 4], Error> = Deserialize::deserialize(&mut de);
+    assert!(actual.is_err());
+}
+
+#[test]
+fn pass_array_matching_fixed_size() {
+    let buf = [0x94, 0x01, 0x02, 0x03, 0x04];
+    let mut de = Deserializer::new(Cursor::new(&buf[..]));
+
+    let actual: [u32; 4] = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!([1, 2, 3, 4], actual);
+}
+
+#[test]
+fn fail_array_too_long_for_fixed_size_still_drains_reader() {
+    // Two back-to-back msgpack values: a 4-element array, then a string. Decoding the
+    // array into `[u32; 3]` must fail, but it must also consume all 4 array elements so
+    // that the following value can still be read correctly.
+    let mut buf = vec![0x94, 0x01, 0x02, 0x03, 0x04];
+    buf.extend(crate::rmps::to_vec(&"next").unwrap());
+    let mut de = Deserializer::new(Cursor::new(&buf[..]));
+
+    let actual: Result<[u32; 3], Error> = Deserialize::deserialize(&mut de);
+    assert!(actual.is_err());
+
+    let next: String = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!("next", next);
+}
+
 #[test]
 fn pass_option_some() {
     let buf = [0x1f];
@@ -376,6 +425,21 @@ fn pass_bin32_into_bytebuf() {
     assert_eq!([0xcc, 0x80], actual[..]);
 }
 
+#[test]
+fn pass_large_bin32_into_bytebuf() {
+    use serde_bytes::ByteBuf;
+
+    let payload: Vec<u8> = (0..1_000_000u32).map(|i| (i % 256) as u8).collect();
+    let mut buf = vec![0xc6];
+    buf.extend((payload.len() as u32).to_be_bytes());
+    buf.extend_from_slice(&payload);
+
+    let mut de = Deserializer::new(&buf[..]);
+    let actual: ByteBuf = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(payload, actual.into_vec());
+}
+
 #[test]
 fn pass_bin8_into_bytebuf_regression_growing_buffer() {
     use serde_bytes::ByteBuf;
@@ -435,6 +499,78 @@ fn test_deserialize_numeric() {
     assert_eq!(x, FloatOrInteger::Integer(36));
 }
 
+#[test]
+fn pass_ext_handler_decodes_little_endian_u64() {
+    struct AnyU64(u64);
+
+    impl<'de> de::Deserialize<'de> for AnyU64 {
+        fn deserialize<D>(de: D) -> Result<AnyU64, D::Error>
+            where D: de::Deserializer<'de>
+        {
+            struct AnyU64Visitor;
+
+            impl<'de> de::Visitor<'de> for AnyU64Visitor {
+                type Value = AnyU64;
+
+                fn expecting(&self, fmt: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+                    write!(fmt, "an ext type decoded by a registered handler")
+                }
+
+                fn visit_u64<E>(self, value: u64) -> Result<AnyU64, E> {
+                    Ok(AnyU64(value))
+                }
+            }
+            de.deserialize_any(AnyU64Visitor)
+        }
+    }
+
+    let mut buf = vec![0xd7, 42]; // FixExt8, tag 42
+    buf.extend_from_slice(&0x0102030405060708u64.to_le_bytes());
+
+    let mut de = Deserializer::new(&buf[..]).with_ext_handler(42, |bytes: &[u8]| {
+        let arr: [u8; 8] = bytes.try_into()
+            .map_err(|_| decode::Error::Uncategorized("expected 8 bytes".to_owned()))?;
+        Ok(u64::from_le_bytes(arr))
+    });
+    let x: AnyU64 = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(x.0, 0x0102030405060708);
+}
+
+#[test]
+fn pass_ext_handler_is_not_tried_against_a_different_tag() {
+    let mut buf = vec![0xd7, 7]; // FixExt8, tag 7 (no handler registered for it)
+    buf.extend_from_slice(&[0u8; 8]);
+
+    // Decodes through `deserialize_any`, where handlers are looked up; if the tag-42 handler ran,
+    // the value would arrive via `visit_u64` and this visitor would reject it.
+    struct ExtTag(i8);
+
+    struct ExtTagVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for ExtTagVisitor {
+        type Value = ExtTag;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("an unhandled ext value")
+        }
+
+        fn visit_newtype_struct<D: serde::Deserializer<'de>>(self, de: D) -> Result<ExtTag, D::Error> {
+            let (tag, _bytes): (i8, serde_bytes::ByteBuf) = Deserialize::deserialize(de)?;
+            Ok(ExtTag(tag))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ExtTag {
+        fn deserialize<D: serde::Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+            de.deserialize_any(ExtTagVisitor)
+        }
+    }
+
+    let mut de = Deserializer::new(&buf[..]).with_ext_handler(42, |_bytes: &[u8]| Ok(0));
+    let x: ExtTag = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(x.0, 7);
+}
+
 #[test]
 fn pass_deserializer_get_ref() {
     let buf = [0xc0];
@@ -569,3 +705,527 @@ fn fail_depth_limit() {
         other => panic!("unexpected result: {:?}", other),
     }
 }
+
+#[test]
+fn fail_huge_declared_array_len_is_not_bounded_by_bytes_limit_alone() {
+    // array32 declaring 0xffff_ffff elements (~4 billion), followed by nothing. `array`/`map`
+    // length prefixes declare an element *count*, not a byte length, so `with_bytes_limit` alone
+    // doesn't bound them; use `with_max_array_len` for that (see
+    // `fail_huge_declared_array_len_with_max_array_len` below). Here the declared length is
+    // simply read as-is and deserialization fails once the (absent) elements are read.
+    let buf = [0xdd, 0xff, 0xff, 0xff, 0xff];
+
+    let mut de = Deserializer::new(&buf[..]).with_bytes_limit(1024);
+    let res: Result<Vec<u8>, _> = Deserialize::deserialize(&mut de);
+
+    match res.err().unwrap() {
+        decode::Error::Truncated { .. } => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_small_array_within_bytes_limit() {
+    let buf = [0x93, 0x01, 0x02, 0x03];
+
+    let mut de = Deserializer::new(&buf[..]).with_bytes_limit(1024);
+    let actual: Vec<u8> = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(vec![1, 2, 3], actual);
+}
+
+#[test]
+fn fail_huge_declared_array_len_with_max_array_len() {
+    // array32 declaring 10 million elements, followed by nothing: a payload that would force a
+    // huge amount of per-element work even though each fixint is a single byte.
+    let mut buf = vec![0xdd];
+    buf.extend(10_000_000u32.to_be_bytes());
+
+    let mut de = Deserializer::new(&buf[..]).with_max_array_len(1_000);
+    let res: Result<Vec<u8>, _> = Deserialize::deserialize(&mut de);
+
+    match res.err().unwrap() {
+        decode::Error::ArrayLenExceeded => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn pass_small_array_within_max_array_len() {
+    let buf = [0x93, 0x01, 0x02, 0x03];
+
+    let mut de = Deserializer::new(&buf[..]).with_max_array_len(1_000);
+    let actual: Vec<u8> = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(vec![1, 2, 3], actual);
+}
+
+#[test]
+fn fail_nil_for_vec_without_nil_as_empty_collection() {
+    let buf = [0xc0]; // nil
+
+    let mut de = Deserializer::new(&buf[..]);
+    let res: Result<Vec<u32>, _> = Deserialize::deserialize(&mut de);
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn pass_nil_as_empty_vec_with_nil_as_empty_collection() {
+    let buf = [0xc0]; // nil
+
+    let mut de = Deserializer::new(&buf[..]).with_nil_as_empty_collection();
+    let actual: Vec<u32> = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(Vec::<u32>::new(), actual);
+}
+
+#[test]
+fn pass_nil_as_empty_map_with_nil_as_empty_collection() {
+    use std::collections::HashMap;
+
+    let buf = [0xc0]; // nil
+
+    let mut de = Deserializer::new(&buf[..]).with_nil_as_empty_collection();
+    let actual: HashMap<String, u32> = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(HashMap::new(), actual);
+}
+
+// A stand-in for a `#[serde(with = "...")]` adapter module that reads a fixed-size big integer
+// as raw bytes rather than a sequence, e.g. `#[serde(with = "big_integer")]`.
+mod big_integer {
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D>(de: D) -> Result<&'de [u8], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <&[u8]>::deserialize(de)
+    }
+}
+
+#[test]
+fn pass_bin32_field_borrows_from_slice_without_copying() {
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    struct BigInteger<'a> {
+        #[serde(with = "big_integer")]
+        digits: &'a [u8],
+    }
+
+    // A 1-element array wrapping a bin32 payload: the wire shape `BigInteger` derives for its
+    // single field.
+    let mut buf = vec![0x91, 0xc6, 0x00, 0x00, 0x00, 0x20];
+    buf.extend((0..32u8).collect::<Vec<u8>>());
+
+    let mut de = Deserializer::from_read_ref(&buf);
+    let actual: BigInteger = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(&buf[6..], actual.digits);
+    // A genuine borrow points straight into `buf`'s own allocation rather than a copy of it.
+    assert_eq!(buf[6..].as_ptr(), actual.digits.as_ptr());
+}
+
+#[test]
+fn pass_two_str_fields_borrow_from_read_ref_without_copying() {
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Pair<'a> {
+        first: &'a str,
+        second: &'a str,
+    }
+
+    let buf = crate::rmps::to_vec(&Pair { first: "hello", second: "world" }).unwrap();
+
+    let mut de = Deserializer::from_read_ref(&buf);
+    let actual: Pair = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(actual, Pair { first: "hello", second: "world" });
+    // Both fields point straight into `buf`'s own allocation rather than a copy of it.
+    let first_offset = actual.first.as_ptr() as usize - buf.as_ptr() as usize;
+    let second_offset = actual.second.as_ptr() as usize - buf.as_ptr() as usize;
+    assert!(first_offset < buf.len());
+    assert!(second_offset < buf.len());
+}
+
+#[test]
+fn pass_cow_str_field_borrows_via_borrow_cow_str_from_slice() {
+    use std::borrow::Cow;
+
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Message<'a> {
+        #[serde(borrow, deserialize_with = "crate::rmps::decode::borrow_cow_str")]
+        text: Cow<'a, str>,
+    }
+
+    let buf = crate::rmps::to_vec(&"hello").unwrap();
+
+    let mut de = Deserializer::from_read_ref(&buf);
+    let actual: Cow<str> = decode::borrow_cow_str(&mut de).unwrap();
+
+    assert_eq!(actual, Cow::Borrowed("hello"));
+    assert!(matches!(actual, Cow::Borrowed(_)));
+
+    let buf = crate::rmps::to_vec(&Message { text: Cow::Borrowed("world") }).unwrap();
+    let mut de = Deserializer::from_read_ref(&buf);
+    let actual: Message = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(actual, Message { text: Cow::Borrowed("world") });
+    assert!(matches!(actual.text, Cow::Borrowed(_)));
+}
+
+#[test]
+fn pass_peek_marker_then_deserialize_struct_from_map() {
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    // A fixmap with two string-keyed fields: the wire shape a struct-as-map producer emits.
+    let buf = vec![
+        0x82, // fixmap(2)
+        0xa1, b'x', 0x01, // "x": 1
+        0xa1, b'y', 0x02, // "y": 2
+    ];
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!(Marker::FixMap(2), de.peek_marker().unwrap());
+    // Peeking again returns the same marker without consuming any input.
+    assert_eq!(Marker::FixMap(2), de.peek_marker().unwrap());
+
+    let actual: Point = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Point { x: 1, y: 2 }, actual);
+}
+
+#[test]
+fn fail_truncated_input_yields_truncated_error_not_generic_io_error() {
+    // A fixstr(5) "hello": marker byte, then 5 payload bytes.
+    let buf = vec![0xa5, b'h', b'e', b'l', b'l', b'o'];
+
+    // Cut off before the marker is even read.
+    let mut de = Deserializer::new(&buf[..0]);
+    assert!(matches!(
+        String::deserialize(&mut de),
+        Err(Error::Truncated { .. })
+    ));
+
+    // Cut off partway through the payload.
+    for end in 1..buf.len() {
+        let mut de = Deserializer::new(&buf[..end]);
+        match String::deserialize(&mut de) {
+            Err(Error::Truncated { .. }) => {}
+            other => panic!("expected Error::Truncated at truncation offset {}, got {:?}", end, other),
+        }
+    }
+
+    // The untruncated buffer decodes fine.
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!("hello".to_string(), String::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_float_into_integer_field_without_float_to_int() {
+    // float64(5.0)
+    let buf = [0xcb, 0x40, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    let mut de = Deserializer::new(&buf[..]);
+    assert!(u32::deserialize(&mut de).is_err());
+}
+
+#[test]
+fn pass_whole_float_into_integer_field_with_float_to_int() {
+    // float64(5.0)
+    let buf = [0xcb, 0x40, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    let mut de = Deserializer::new(&buf[..]).with_float_to_int();
+    assert_eq!(5u32, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_fractional_float_into_integer_field_with_float_to_int() {
+    // float64(5.5)
+    let buf = [0xcb, 0x40, 0x16, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+    let mut de = Deserializer::new(&buf[..]).with_float_to_int();
+    assert!(u32::deserialize(&mut de).is_err());
+}
+
+#[test]
+fn pass_whole_float32_into_integer_field_with_float_to_int() {
+    // float32(5.0)
+    let buf = [0xca, 0x40, 0xa0, 0x00, 0x00];
+    let mut de = Deserializer::new(&buf[..]).with_float_to_int();
+    assert_eq!(5i64, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_out_of_range_float_into_integer_field_with_float_to_int() {
+    // float64(1e30), far too large for a u8
+    let buf = [0xcb, 0x46, 0x29, 0x3e, 0x59, 0x39, 0xa0, 0x8c, 0xea];
+    let mut de = Deserializer::new(&buf[..]).with_float_to_int();
+    assert!(u8::deserialize(&mut de).is_err());
+}
+
+#[test]
+fn pass_plain_integer_still_decodes_with_float_to_int() {
+    let buf = [0x2a]; // fixint(42)
+    let mut de = Deserializer::new(&buf[..]).with_float_to_int();
+    assert_eq!(42u32, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_missing_trailing_option_field_with_trailing_optional() {
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Triple {
+        a: u8,
+        b: u8,
+        c: Option<u8>,
+    }
+
+    // A 2-element array: the third field, `c`, is omitted entirely.
+    let buf = vec![
+        0x92, // fixarray(2)
+        0x01, // a: 1
+        0x02, // b: 2
+    ];
+
+    let mut de = Deserializer::new(&buf[..]).with_trailing_optional();
+    let actual: Triple = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Triple { a: 1, b: 2, c: None }, actual);
+}
+
+#[test]
+fn fail_missing_trailing_option_field_without_trailing_optional() {
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    struct Triple {
+        a: u8,
+        b: u8,
+        c: Option<u8>,
+    }
+
+    let buf = vec![0x92, 0x01, 0x02]; // fixarray(2): a: 1, b: 2, `c` omitted
+
+    let mut de = Deserializer::new(&buf[..]);
+    let res: Result<Triple, Error> = Deserialize::deserialize(&mut de);
+    assert!(res.is_err());
+}
+
+#[test]
+fn pass_full_array_still_decodes_with_trailing_optional() {
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Triple {
+        a: u8,
+        b: u8,
+        c: Option<u8>,
+    }
+
+    let value = Triple { a: 1, b: 2, c: Some(3) };
+    let buf = crate::rmps::to_vec(&value).unwrap();
+
+    let mut de = Deserializer::new(&buf[..]).with_trailing_optional();
+    assert_eq!(value, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_missing_required_trailing_field_with_trailing_optional() {
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    struct Triple {
+        a: u8,
+        b: u8,
+        c: u8,
+    }
+
+    // `c` isn't `Option` or `#[serde(default)]`, so it still can't be omitted.
+    let buf = vec![0x92, 0x01, 0x02]; // fixarray(2): a: 1, b: 2
+
+    let mut de = Deserializer::new(&buf[..]).with_trailing_optional();
+    let res: Result<Triple, Error> = Deserialize::deserialize(&mut de);
+    assert!(res.is_err());
+}
+
+// `Shape` deliberately relies on `with_unknown_variant_fallback` rather than `#[serde(other)]`:
+// serde's own `#[serde(other)]` already accepts any unrecognized name unconditionally, so it
+// can't be used to demonstrate this flag's effect. A plain unit variant serialized as `"other"`
+// (renamed here rather than named `other`, to keep an upper camel case identifier), with no
+// `#[serde(other)]` attribute, is what `with_unknown_variant_fallback` routes an unrecognized
+// wire name to.
+#[derive(Debug, PartialEq, serde_derive::Deserialize)]
+enum Shape {
+    Circle,
+    #[serde(rename = "other")]
+    Other,
+}
+
+#[test]
+fn pass_unknown_variant_decodes_into_other_with_unknown_variant_fallback() {
+    // A 1-entry map `{"Square": nil}`: the variant name isn't one `Shape` declares.
+    let buf = vec![
+        0x81, // fixmap(1)
+        0xa6, b'S', b'q', b'u', b'a', b'r', b'e', // "Square"
+        0xc0, // nil
+    ];
+
+    let mut de = Deserializer::new(&buf[..]).with_unknown_variant_fallback();
+    let actual: Shape = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Shape::Other, actual);
+}
+
+#[test]
+fn fail_unknown_variant_without_unknown_variant_fallback() {
+    let buf = vec![
+        0x81, // fixmap(1)
+        0xa6, b'S', b'q', b'u', b'a', b'r', b'e', // "Square"
+        0xc0, // nil
+    ];
+
+    let mut de = Deserializer::new(&buf[..]);
+    let res: Result<Shape, Error> = Deserialize::deserialize(&mut de);
+    assert!(res.is_err());
+}
+
+#[test]
+fn pass_known_variant_still_decodes_with_unknown_variant_fallback() {
+    let buf = vec![
+        0x81, // fixmap(1)
+        0xa6, b'C', b'i', b'r', b'c', b'l', b'e', // "Circle"
+        0xc0, // nil
+    ];
+
+    let mut de = Deserializer::new(&buf[..]).with_unknown_variant_fallback();
+    let actual: Shape = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Shape::Circle, actual);
+}
+
+#[test]
+fn pass_skipped_field_ignores_large_nested_value() {
+    use crate::rmps::Serializer;
+    use serde::Serialize;
+
+    #[derive(Debug, PartialEq, serde_derive::Serialize)]
+    struct Wire {
+        id: u32,
+        // A large nested value the target struct below doesn't want, encoded by name so the
+        // `Target` struct's field-name matching (not array-length matching) is what routes it
+        // through `deserialize_ignored_any`.
+        payload: Vec<Vec<u8>>,
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    struct Target {
+        id: u32,
+        #[serde(skip)]
+        payload: (),
+        name: String,
+    }
+
+    let wire = Wire {
+        id: 7,
+        payload: (0..1000).map(|i| vec![i as u8; 64]).collect(),
+        name: "small".to_owned(),
+    };
+    let mut buf = Vec::new();
+    wire.serialize(&mut Serializer::new(&mut buf).with_struct_map()).unwrap();
+
+    let mut de = Deserializer::new(&buf[..]);
+    let actual: Target = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Target { id: 7, payload: (), name: "small".to_owned() }, actual);
+}
+
+#[test]
+fn pass_nonzero_types_round_trip() {
+    use std::num::{NonZeroI64, NonZeroU8, NonZeroU32};
+
+    let a = NonZeroU8::new(1).unwrap();
+    let buf = crate::rmps::to_vec(&a).unwrap();
+    assert_eq!(a, Deserialize::deserialize(&mut Deserializer::new(&buf[..])).unwrap());
+
+    let b = NonZeroU32::new(u32::MAX).unwrap();
+    let buf = crate::rmps::to_vec(&b).unwrap();
+    assert_eq!(b, Deserialize::deserialize(&mut Deserializer::new(&buf[..])).unwrap());
+
+    let c = NonZeroI64::new(-42).unwrap();
+    let buf = crate::rmps::to_vec(&c).unwrap();
+    assert_eq!(c, Deserialize::deserialize(&mut Deserializer::new(&buf[..])).unwrap());
+}
+
+#[test]
+fn fail_zero_decoded_into_nonzero_u32() {
+    use std::num::NonZeroU32;
+
+    let buf = crate::rmps::to_vec(&0u32).unwrap();
+    let mut de = Deserializer::new(&buf[..]);
+    let res: Result<NonZeroU32, Error> = Deserialize::deserialize(&mut de);
+    assert!(res.is_err());
+}
+
+#[test]
+fn pass_integer_keyed_map_decodes_positionally_with_lenient_map_to_struct_ordering() {
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+        z: i32,
+    }
+
+    // A fixmap(3) with plain integer keys that don't line up with either field name or field
+    // index: a producer emitting arbitrary numeric tags, relying only on entry order.
+    let buf = vec![
+        0x83, // fixmap(3)
+        0x05, 0x01, // 5: 1
+        0x09, 0x02, // 9: 2
+        0x0a, 0x03, // 10: 3
+    ];
+
+    let mut de = Deserializer::new(&buf[..]).with_lenient_map_to_struct_ordering();
+    let actual: Point = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Point { x: 1, y: 2, z: 3 }, actual);
+}
+
+#[test]
+fn pass_named_keys_still_match_by_name_with_lenient_map_to_struct_ordering() {
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    // Field names present, but out of the struct's declared order: name matching must win over
+    // positional fallback, or this would decode as `Point { x: 2, y: 1 }` instead.
+    let buf = vec![
+        0x82, // fixmap(2)
+        0xa1, b'y', 0x01, // "y": 1
+        0xa1, b'x', 0x02, // "x": 2
+    ];
+
+    let mut de = Deserializer::new(&buf[..]).with_lenient_map_to_struct_ordering();
+    let actual: Point = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Point { x: 2, y: 1 }, actual);
+}
+
+#[test]
+fn fail_float32_into_f64_with_strict_float_width() {
+    // fixarray(1) wrapping a single float32(1.5).
+    let buf = [0x91, 0xca, 0x3f, 0xc0, 0x00, 0x00];
+
+    let mut de = Deserializer::new(&buf[..]).with_strict_float_width();
+    let err = <(f64,)>::deserialize(&mut de).unwrap_err();
+    assert_eq!("wrong msgpack marker F32", err.to_string());
+}
+
+#[test]
+fn pass_float32_into_f32_with_strict_float_width() {
+    // fixarray(1) wrapping a single float32(1.5).
+    let buf = [0x91, 0xca, 0x3f, 0xc0, 0x00, 0x00];
+
+    let mut de = Deserializer::new(&buf[..]).with_strict_float_width();
+    let (actual,): (f32,) = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(1.5f32, actual);
+}
+
+#[test]
+fn pass_float32_into_f64_without_strict_float_width() {
+    // Default behavior is unchanged: a float32 still widens into an f64 field.
+    let buf = [0x91, 0xca, 0x3f, 0xc0, 0x00, 0x00];
+
+    let mut de = Deserializer::new(&buf[..]);
+    let (actual,): (f64,) = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(1.5f64, actual);
+}
@@ -535,3 +535,256 @@ fn pass_from_ref() {
 
     assert_eq!(Dog { name: "Bobby", age: 8 }, rmps::from_read_ref(&buf).unwrap());
 }
+
+#[test]
+fn pass_struct_as_map_with_integer_keys_matching_declaration_order() {
+    // A compact encoder may write struct-as-map data keyed by field index rather than name,
+    // e.g. `{0: 42, 1: "hi"}` instead of `{"a": 42, "b": "hi"}`. `#[derive(Deserialize)]`
+    // already resolves such integer keys positionally against the struct's declared field
+    // order, with no extra configuration needed on the `Deserializer`.
+    let buf = [0x82, 0x00, 0x2a, 0x01, 0xa2, 0x68, 0x69];
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Foo {
+        a: u32,
+        b: String,
+    }
+
+    assert_eq!(Foo { a: 42, b: "hi".into() }, rmps::from_slice(&buf).unwrap());
+}
+
+#[test]
+fn pass_lenient_enum_decodes_map_and_array_forms() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Animal {
+        Cat(u32),
+    }
+
+    // {"Cat": 3}
+    let as_map = [0x81, 0xa3, 0x43, 0x61, 0x74, 0x03];
+    // ["Cat", 3]
+    let as_array = [0x92, 0xa3, 0x43, 0x61, 0x74, 0x03];
+
+    let mut de = Deserializer::new(&as_map[..]).with_lenient_enums();
+    assert_eq!(Animal::Cat(3), Animal::deserialize(&mut de).unwrap());
+
+    let mut de = Deserializer::new(&as_array[..]).with_lenient_enums();
+    assert_eq!(Animal::Cat(3), Animal::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn fail_array_form_enum_without_lenient_enums() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Animal {
+        Cat(u32),
+    }
+
+    // ["Cat", 3], decoded without opting into `with_lenient_enums`.
+    let as_array = [0x92, 0xa3, 0x43, 0x61, 0x74, 0x03];
+    let res: Result<Animal, _> = rmps::from_slice(&as_array);
+    assert!(res.is_err());
+}
+
+#[test]
+fn pass_enum_repr_autodetect_decodes_string_integer_and_map_forms() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Animal {
+        Cat,
+        Dog(u32),
+    }
+
+    // "Cat" written as a bare string, the "string variant" form.
+    let as_str = [0xa3, 0x43, 0x61, 0x74];
+    // 0 written as a bare integer, the "integer variant" form (`Cat` is variant index 0).
+    let as_int = [0x00];
+    // {"Dog": 3}, the externally-tagged map form carrying data.
+    let as_map = [0x81, 0xa3, 0x44, 0x6f, 0x67, 0x03];
+
+    let mut de = Deserializer::new(&as_str[..]).with_enum_repr_autodetect();
+    assert_eq!(Animal::Cat, Animal::deserialize(&mut de).unwrap());
+
+    let mut de = Deserializer::new(&as_int[..]).with_enum_repr_autodetect();
+    assert_eq!(Animal::Cat, Animal::deserialize(&mut de).unwrap());
+
+    let mut de = Deserializer::new(&as_map[..]).with_enum_repr_autodetect();
+    assert_eq!(Animal::Dog(3), Animal::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_enum_repr_autodetect_also_accepts_lenient_array_form() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Animal {
+        Cat(u32),
+    }
+
+    // ["Cat", 3], normally gated behind `with_lenient_enums` alone.
+    let as_array = [0x92, 0xa3, 0x43, 0x61, 0x74, 0x03];
+
+    let mut de = Deserializer::new(&as_array[..]).with_enum_repr_autodetect();
+    assert_eq!(Animal::Cat(3), Animal::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_duplicate_map_key_last_wins_without_reject_duplicate_keys() {
+    use std::collections::HashMap;
+
+    // {"name": "a", "name": "b"}
+    let bytes = [
+        0x82,
+        0xa4, 0x6e, 0x61, 0x6d, 0x65, 0xa1, 0x61,
+        0xa4, 0x6e, 0x61, 0x6d, 0x65, 0xa1, 0x62,
+    ];
+
+    let map: HashMap<String, String> = rmps::from_slice(&bytes).unwrap();
+    assert_eq!(map.get("name").map(String::as_str), Some("b"));
+}
+
+#[test]
+fn fail_duplicate_map_key_with_reject_duplicate_keys() {
+    use std::collections::HashMap;
+
+    // {"name": "a", "name": "b"}
+    let bytes = [
+        0x82,
+        0xa4, 0x6e, 0x61, 0x6d, 0x65, 0xa1, 0x61,
+        0xa4, 0x6e, 0x61, 0x6d, 0x65, 0xa1, 0x62,
+    ];
+
+    let mut de = Deserializer::new(&bytes[..]).with_reject_duplicate_keys();
+    let res: Result<HashMap<String, String>, _> = Deserialize::deserialize(&mut de);
+    assert!(res.is_err());
+}
+
+#[test]
+fn pass_struct_as_map_matches_serde_alias() {
+    #[derive(Debug, Serialize)]
+    struct Encoded {
+        old_name: u32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Decoded {
+        #[serde(alias = "old_name")]
+        new_name: u32,
+    }
+
+    let buf = rmps::to_vec_named(&Encoded { old_name: 7 }).unwrap();
+
+    let mut de = Deserializer::new(&buf[..]);
+    let actual: Decoded = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Decoded { new_name: 7 }, actual);
+}
+
+#[test]
+fn pass_duplicate_struct_field_first_wins_with_first_wins_policy() {
+    use crate::rmps::decode::DuplicateKeyPolicy;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Decoded {
+        age: u32,
+    }
+
+    // {"age": 1, "age": 2}
+    let bytes = [
+        0x82,
+        0xa3, 0x61, 0x67, 0x65, 0x01,
+        0xa3, 0x61, 0x67, 0x65, 0x02,
+    ];
+
+    let mut de = Deserializer::new(&bytes[..]).with_duplicate_key_policy(DuplicateKeyPolicy::FirstWins);
+    let actual: Decoded = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(Decoded { age: 1 }, actual);
+}
+
+#[test]
+fn fail_duplicate_struct_field_with_last_wins_policy() {
+    use crate::rmps::decode::DuplicateKeyPolicy;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Decoded {
+        age: u32,
+    }
+
+    // {"age": 1, "age": 2}; `LastWins` lets both occurrences reach the derived `Visitor`, which
+    // always rejects seeing the same field twice, regardless of the policy's name.
+    let bytes = [
+        0x82,
+        0xa3, 0x61, 0x67, 0x65, 0x01,
+        0xa3, 0x61, 0x67, 0x65, 0x02,
+    ];
+
+    let mut de = Deserializer::new(&bytes[..]).with_duplicate_key_policy(DuplicateKeyPolicy::LastWins);
+    let res: Result<Decoded, _> = Deserialize::deserialize(&mut de);
+    assert!(res.is_err());
+}
+
+#[test]
+fn fail_duplicate_struct_field_with_error_policy() {
+    use crate::rmps::decode::DuplicateKeyPolicy;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Decoded {
+        age: u32,
+    }
+
+    // {"age": 1, "age": 2}
+    let bytes = [
+        0x82,
+        0xa3, 0x61, 0x67, 0x65, 0x01,
+        0xa3, 0x61, 0x67, 0x65, 0x02,
+    ];
+
+    let mut de = Deserializer::new(&bytes[..]).with_duplicate_key_policy(DuplicateKeyPolicy::Error);
+    let res: Result<Decoded, _> = Deserialize::deserialize(&mut de);
+    assert!(matches!(res, Err(Error::Uncategorized(_))));
+}
+
+#[test]
+fn fail_tuple_struct_array_too_short() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Decoded {
+        id: u32,
+        value: u32,
+    }
+
+    // A 1-element array, but `Decoded` needs 2 fields and neither has `#[serde(default)]`.
+    let buf = [0x91, 0x2a];
+
+    let mut de = Deserializer::new(&buf[..]);
+    let res: Result<Decoded, _> = Deserialize::deserialize(&mut de);
+    assert!(res.is_err());
+}
+
+#[test]
+fn pass_tuple_struct_array_too_short_uses_default() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Decoded {
+        id: u32,
+        #[serde(default)]
+        value: u32,
+    }
+
+    // A 1-element array; the missing trailing field falls back to its `Default`.
+    let buf = [0x91, 0x2a];
+
+    let mut de = Deserializer::new(&buf[..]);
+    let actual: Decoded = Deserialize::deserialize(&mut de).unwrap();
+
+    assert_eq!(Decoded { id: 42, value: 0 }, actual);
+}
+
+#[test]
+fn fail_tuple_struct_array_too_long() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Decoded {
+        id: u32,
+        value: u32,
+    }
+
+    // A 3-element array, one more than `Decoded`'s 2 fields.
+    let buf = [0x93, 0x2a, 0x7b, 0x01];
+
+    let mut de = Deserializer::new(&buf[..]);
+    let res: Result<Decoded, _> = Deserialize::deserialize(&mut de);
+    assert!(matches!(res, Err(Error::LengthMismatch(2))));
+}
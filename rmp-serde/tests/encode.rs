@@ -368,3 +368,862 @@ fn pass_raw_ref_invalid_utf8() {
 fn serializer_one_type_arg() {
     let _s: rmp_serde::Serializer<&mut dyn std::io::Write>;
 }
+
+#[test]
+fn timestamp_uses_32bit_form_for_whole_seconds() {
+    use crate::rmps::Timestamp;
+
+    let ts = Timestamp::new(42, 0);
+    let buf = crate::rmps::to_vec(&ts).unwrap();
+
+    // fixext4, tag -1, big-endian u32 seconds.
+    assert_eq!(vec![0xd6, 0xff, 0x00, 0x00, 0x00, 0x2a], buf);
+    assert_eq!(ts, crate::rmps::from_slice(&buf).unwrap());
+}
+
+#[test]
+fn timestamp_uses_64bit_form_when_nanos_present() {
+    use crate::rmps::Timestamp;
+
+    let ts = Timestamp::new(42, 7);
+    let buf = crate::rmps::to_vec(&ts).unwrap();
+
+    // fixext8, tag -1.
+    assert_eq!(vec![0xd7, 0xff], &buf[..2]);
+    assert_eq!(8, buf.len() - 2);
+    assert_eq!(ts, crate::rmps::from_slice(&buf).unwrap());
+}
+
+#[test]
+fn timestamp_uses_96bit_form_for_negative_seconds() {
+    use crate::rmps::Timestamp;
+
+    let ts = Timestamp::new(-1, 7);
+    let buf = crate::rmps::to_vec(&ts).unwrap();
+
+    // ext8, length 12, tag -1.
+    assert_eq!(vec![0xc7, 0x0c, 0xff], &buf[..3]);
+    assert_eq!(ts, crate::rmps::from_slice(&buf).unwrap());
+}
+
+#[test]
+fn timestamp_uses_96bit_form_at_34bit_seconds_boundary() {
+    use crate::rmps::Timestamp;
+
+    // The largest second value the 64-bit form can hold is `2^34 - 1`; one past that must
+    // fall back to the 96-bit form.
+    let at_boundary = Timestamp::new((1i64 << 34) - 1, 1);
+    let past_boundary = Timestamp::new(1i64 << 34, 1);
+
+    let at_boundary_buf = crate::rmps::to_vec(&at_boundary).unwrap();
+    let past_boundary_buf = crate::rmps::to_vec(&past_boundary).unwrap();
+
+    assert_eq!(0xd7, at_boundary_buf[0]); // fixext8
+    assert_eq!(0xc7, past_boundary_buf[0]); // ext8
+
+    assert_eq!(at_boundary, crate::rmps::from_slice(&at_boundary_buf).unwrap());
+    assert_eq!(past_boundary, crate::rmps::from_slice(&past_boundary_buf).unwrap());
+}
+
+#[test]
+fn pass_struct_as_map_sorted_by_field_name() {
+    #[derive(serde_derive::Serialize)]
+    struct Dog {
+        b: u16,
+        a: String,
+    }
+
+    let dog = Dog {
+        b: 42,
+        a: "Frankie".into(),
+    };
+
+    let serialized = crate::rmps::to_vec_named_sorted(&dog).unwrap();
+
+    // The map must list `a` before `b`, even though the struct declares `b` first.
+    let mut expected = vec![0x82]; // fixmap of length 2
+    expected.extend(crate::rmps::to_vec(&"a").unwrap());
+    expected.extend(crate::rmps::to_vec(&"Frankie").unwrap());
+    expected.extend(crate::rmps::to_vec(&"b").unwrap());
+    expected.extend(crate::rmps::to_vec(&42u16).unwrap());
+
+    assert_eq!(expected, serialized);
+}
+
+#[test]
+fn pass_seq_with_unknown_len_from_lazy_iterator() {
+    struct FilteredCount(std::ops::RangeFrom<u32>);
+
+    impl Serialize for FilteredCount {
+        fn serialize<S: serde::Serializer>(&self, se: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeSeq;
+
+            // `Iterator::size_hint` can't tell us how many items pass the filter, so the
+            // sequence is serialized with an unknown length and buffered internally.
+            let mut seq = se.serialize_seq(None)?;
+            for n in self.0.clone().filter(|n| n % 3 == 0).take(4) {
+                seq.serialize_element(&n)?;
+            }
+            seq.end()
+        }
+    }
+
+    let val = FilteredCount(0..);
+    let serialized = crate::rmps::to_vec(&val).unwrap();
+
+    let expected = crate::rmps::to_vec(&(0u32, 3u32, 6u32, 9u32)).unwrap();
+    assert_eq!(expected, serialized);
+}
+
+#[test]
+fn pass_struct_as_map_with_interned_field_names_round_trips_and_shrinks() {
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, PartialEq, Debug)]
+    struct Sample {
+        identifier: u32,
+        description: String,
+        active: bool,
+    }
+
+    let records: Vec<Sample> = (0..1000)
+        .map(|i| Sample { identifier: i, description: format!("record-{}", i), active: i % 2 == 0 })
+        .collect();
+
+    let mut interned = Vec::new();
+    records
+        .serialize(&mut Serializer::new(&mut interned).with_string_interning())
+        .unwrap();
+
+    let plain = crate::rmps::to_vec_named(&records).unwrap();
+
+    // Every record after the first should have its 3 field names replaced by 3-byte
+    // back-references instead of the full names, so the interned encoding is noticeably smaller.
+    assert!(interned.len() < plain.len());
+
+    let mut de = crate::rmps::Deserializer::new(&interned[..]).with_string_interning();
+    let round_tripped: Vec<Sample> = serde::Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(records, round_tripped);
+}
+
+#[test]
+fn pass_enum_with_integer_variants_round_trips_and_shrinks() {
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, PartialEq, Debug)]
+    enum Animal {
+        Emu,
+        Dog { breed: String },
+        Cat(u32),
+    }
+
+    let values = vec![
+        Animal::Emu,
+        Animal::Dog { breed: "Pitbull".to_owned() },
+        Animal::Cat(3),
+    ];
+
+    let mut compact = Vec::new();
+    values
+        .serialize(&mut Serializer::new(&mut compact).with_integer_variants())
+        .unwrap();
+
+    let named = crate::rmps::to_vec(&values).unwrap();
+
+    // Every variant identifier is a 1-byte integer instead of the full variant name.
+    assert!(compact.len() < named.len());
+
+    let round_tripped: Vec<Animal> = crate::rmps::from_slice(&compact).unwrap();
+    assert_eq!(values, round_tripped);
+}
+
+#[test]
+fn fail_seq_serialize_impl_that_under_delivers_elements() {
+    struct BuggySeq;
+
+    impl Serialize for BuggySeq {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer
+        {
+            use serde::ser::SerializeSeq;
+
+            // Declares 3 elements but only ever serializes 2.
+            let mut seq = serializer.serialize_seq(Some(3))?;
+            seq.serialize_element(&1)?;
+            seq.serialize_element(&2)?;
+            seq.end()
+        }
+    }
+
+    let mut buf = Vec::new();
+    let err = BuggySeq.serialize(&mut Serializer::new(&mut buf)).unwrap_err();
+
+    assert!(matches!(err, Error::LengthMismatch(3, 2)));
+}
+
+#[test]
+fn pass_ext_of_16_bytes_uses_fixext16_not_ext8() {
+    #[derive(serde_derive::Serialize)]
+    #[serde(rename = "_ExtStruct")]
+    struct ExtStruct((i8, serde_bytes::ByteBuf));
+
+    let payload = vec![0x2a; 16];
+    let val = ExtStruct((7, serde_bytes::ByteBuf::from(payload.clone())));
+
+    let buf = crate::rmps::to_vec(&val).unwrap();
+
+    // fixext16 is a single marker byte (0xd8) followed by the 1-byte type and the 16-byte
+    // payload, whereas ext8 would additionally spell out the length as its own byte.
+    let mut expected = vec![0xd8, 7];
+    expected.extend_from_slice(&payload);
+    assert_eq!(expected, buf);
+}
+
+#[test]
+fn pass_unit_as_empty_array() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let mut buf = Vec::new();
+    ().serialize(&mut Serializer::new(&mut buf).with_unit_as_empty_array()).unwrap();
+
+    assert_eq!(vec![0x90], buf); // fixarray(0), not nil
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!((), Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_unit_struct_as_empty_array_under_unit_as_empty_array() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    #[derive(Debug, serde_derive::Serialize, serde_derive::Deserialize, PartialEq, Eq)]
+    struct Empty;
+
+    let mut buf = Vec::new();
+    Empty.serialize(&mut Serializer::new(&mut buf).with_unit_as_empty_array()).unwrap();
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!(Empty, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_v4_compat_string_uses_legacy_raw_marker() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let mut buf = Vec::new();
+    "the Answer".serialize(&mut Serializer::new(&mut buf).with_v4_compat()).unwrap();
+
+    // Identical to the plain `str` encoding: the pre-2013 `raw` marker for a short string and
+    // the modern `fixstr` marker occupy the same byte range.
+    assert_eq!(vec![0xaa, b't', b'h', b'e', b' ', b'A', b'n', b's', b'w', b'e', b'r'], buf);
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!("the Answer".to_string(), String::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_v4_compat_bytes_uses_legacy_raw_marker_instead_of_bin() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+    use serde_bytes::{ByteBuf, Bytes};
+
+    let mut buf = Vec::new();
+    Bytes::new(&[0xcc, 0x80]).serialize(&mut Serializer::new(&mut buf).with_v4_compat()).unwrap();
+
+    // No `bin8` marker (0xc4): the payload is written as a 2-byte `fixstr` instead, since the
+    // legacy spec this mode targets has no `bin` type at all.
+    assert_eq!(vec![0xa2, 0xcc, 0x80], buf);
+
+    let mut de = Deserializer::new(&buf[..]);
+    let actual: ByteBuf = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(vec![0xcc, 0x80], actual.into_vec());
+}
+
+#[test]
+fn pass_write_flushes_a_buffered_writer() {
+    use std::io::BufWriter;
+
+    let mut buf = Vec::new();
+    let mut wr = BufWriter::new(&mut buf);
+    encode::write(&mut wr, &42u32).unwrap();
+
+    // No explicit flush or drop here: `encode::write` must have flushed already, since `wr` is
+    // still borrowing `buf` and hasn't gone out of scope.
+    assert_eq!(wr.buffer().len(), 0);
+}
+
+#[test]
+fn pass_canonical_nan_normalizes_f64_bit_pattern() {
+    // A signaling NaN: exponent all-ones, quiet bit (the mantissa's MSB) clear, and a nonzero
+    // mantissa so it's still a NaN rather than infinity.
+    let signaling_nan = f64::from_bits(0x7ff0_0000_0000_0001);
+    assert!(signaling_nan.is_nan());
+    assert_ne!(signaling_nan.to_bits(), f64::NAN.to_bits());
+
+    let mut quiet_buf = Vec::new();
+    f64::NAN.serialize(&mut Serializer::new(&mut quiet_buf).with_canonical_nan()).unwrap();
+
+    let mut signaling_buf = Vec::new();
+    signaling_nan.serialize(&mut Serializer::new(&mut signaling_buf).with_canonical_nan()).unwrap();
+
+    assert_eq!(quiet_buf, signaling_buf);
+
+    // Without the flag, the two distinct bit patterns produce distinct output.
+    let mut plain_quiet_buf = Vec::new();
+    f64::NAN.serialize(&mut Serializer::new(&mut plain_quiet_buf)).unwrap();
+
+    let mut plain_signaling_buf = Vec::new();
+    signaling_nan.serialize(&mut Serializer::new(&mut plain_signaling_buf)).unwrap();
+
+    assert_ne!(plain_quiet_buf, plain_signaling_buf);
+}
+
+#[test]
+fn pass_canonical_nan_normalizes_f32_bit_pattern() {
+    let signaling_nan = f32::from_bits(0x7f80_0001);
+    assert!(signaling_nan.is_nan());
+    assert_ne!(signaling_nan.to_bits(), f32::NAN.to_bits());
+
+    let mut quiet_buf = Vec::new();
+    f32::NAN.serialize(&mut Serializer::new(&mut quiet_buf).with_canonical_nan()).unwrap();
+
+    let mut signaling_buf = Vec::new();
+    signaling_nan.serialize(&mut Serializer::new(&mut signaling_buf).with_canonical_nan()).unwrap();
+
+    assert_eq!(quiet_buf, signaling_buf);
+}
+
+#[test]
+fn pass_canonical_nan_leaves_non_nan_floats_untouched() {
+    let mut buf = Vec::new();
+    (-0.0f64).serialize(&mut Serializer::new(&mut buf).with_canonical_nan()).unwrap();
+
+    let mut expected = Vec::new();
+    (-0.0f64).serialize(&mut Serializer::new(&mut expected)).unwrap();
+
+    assert_eq!(expected, buf);
+}
+
+#[test]
+fn pass_enum_struct_variant_with_flattened_field_round_trips() {
+    use std::collections::BTreeMap;
+
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize, PartialEq, Debug)]
+    enum Enum {
+        V {
+            #[serde(flatten)]
+            inner: BTreeMap<String, String>,
+            x: u32,
+        },
+    }
+
+    let value = Enum::V {
+        inner: {
+            let mut map = BTreeMap::new();
+            map.insert("greeting".to_string(), "hello".to_string());
+            map
+        },
+        x: 42,
+    };
+
+    // default (struct-as-tuple) serializer
+    {
+        let serialized = crate::rmps::to_vec(&value).unwrap();
+        let round_tripped: Enum = crate::rmps::from_slice(&serialized).unwrap();
+        assert_eq!(value, round_tripped);
+    }
+
+    // named (struct-as-map) serializer
+    {
+        let serialized = crate::rmps::to_vec_named(&value).unwrap();
+        let round_tripped: Enum = crate::rmps::from_slice(&serialized).unwrap();
+        assert_eq!(value, round_tripped);
+    }
+}
+
+/// A minimal totally-ordered `f64` wrapper, standing in for a crate like `ordered-float` so a
+/// float can be used as a `BTreeMap` key without pulling in a new dependency just for this test.
+#[derive(PartialEq, PartialOrd)]
+struct OrderedFloat(f64);
+
+impl Eq for OrderedFloat {}
+
+impl Ord for OrderedFloat {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).unwrap()
+    }
+}
+
+impl Serialize for OrderedFloat {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.0)
+    }
+}
+
+#[test]
+fn fail_float_map_key_with_error_on_lossy_key() {
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    map.insert(OrderedFloat(1.5), "value");
+
+    let mut buf = Vec::new();
+    let err = map.serialize(&mut Serializer::new(&mut buf).with_error_on_lossy_key()).unwrap_err();
+    assert!(matches!(err, Error::InvalidDataModel(_)));
+}
+
+#[test]
+fn pass_float_map_key_without_error_on_lossy_key() {
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    map.insert(OrderedFloat(1.5), "value");
+
+    let mut buf = Vec::new();
+    map.serialize(&mut Serializer::new(&mut buf)).unwrap();
+}
+
+#[test]
+fn pass_string_map_key_with_error_on_lossy_key() {
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    map.insert("key".to_string(), "value");
+
+    let mut buf = Vec::new();
+    map.serialize(&mut Serializer::new(&mut buf).with_error_on_lossy_key()).unwrap();
+}
+
+#[test]
+fn pass_empty_tuple_struct_round_trips_as_fixarray_by_default() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    #[derive(Debug, serde_derive::Serialize, serde_derive::Deserialize, PartialEq)]
+    struct Empty();
+
+    let mut buf = Vec::new();
+    Empty().serialize(&mut Serializer::new(&mut buf)).unwrap();
+
+    assert_eq!(vec![0x90], buf); // fixarray(0), not nil
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!(Empty(), Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_empty_tuple_struct_round_trips_as_nil_under_empty_tuple_as_nil() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    #[derive(Debug, serde_derive::Serialize, serde_derive::Deserialize, PartialEq)]
+    struct Empty();
+
+    let mut buf = Vec::new();
+    Empty().serialize(&mut Serializer::new(&mut buf).with_empty_tuple_as_nil()).unwrap();
+
+    assert_eq!(vec![0xc0], buf); // nil, not fixarray(0)
+
+    let mut de = Deserializer::new(&buf[..]).with_nil_as_empty_collection();
+    assert_eq!(Empty(), Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_compact_ipv4addr_round_trips_as_4_byte_bin() {
+    use std::net::Ipv4Addr;
+
+    use crate::rmps::{decode, Deserializer};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Peer {
+        #[serde(serialize_with = "encode::compact_ipv4addr", deserialize_with = "decode::compact_ipv4addr")]
+        addr: Ipv4Addr,
+    }
+
+    let peer = Peer { addr: Ipv4Addr::new(192, 168, 0, 1) };
+
+    let mut buf = Vec::new();
+    peer.serialize(&mut Serializer::new(&mut buf)).unwrap();
+
+    // fixarray(1) wrapping the struct's one field, then bin8(4) with the 4 octets.
+    assert_eq!(vec![0x91, 0xc4, 0x04, 192, 168, 0, 1], buf);
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!(peer, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_tuple_round_trips_as_map_with_integer_keys_under_tuple_as_map() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let value: (u8, String) = (7, "hi".to_owned());
+
+    let mut buf = Vec::new();
+    value.serialize(&mut Serializer::new(&mut buf).with_tuple_as_map()).unwrap();
+
+    // fixmap(2): 0 => 7, 1 => "hi"
+    assert_eq!(buf[0], 0x82);
+
+    let mut de = Deserializer::new(&buf[..]).with_tuple_as_map();
+    assert_eq!(value, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_tuple_as_map_still_accepts_plain_array_encoding() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let value: (u8, String) = (7, "hi".to_owned());
+
+    let mut buf = Vec::new();
+    value.serialize(&mut Serializer::new(&mut buf)).unwrap();
+
+    let mut de = Deserializer::new(&buf[..]).with_tuple_as_map();
+    assert_eq!(value, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_compact_ipv6addr_round_trips_as_16_byte_bin() {
+    use std::net::Ipv6Addr;
+
+    use crate::rmps::{decode, Deserializer};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Peer {
+        #[serde(serialize_with = "encode::compact_ipv6addr", deserialize_with = "decode::compact_ipv6addr")]
+        addr: Ipv6Addr,
+    }
+
+    let peer = Peer { addr: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1) };
+
+    let mut buf = Vec::new();
+    peer.serialize(&mut Serializer::new(&mut buf)).unwrap();
+
+    // fixarray(1) wrapping the struct's one field, then bin8(16) with the 16 octets.
+    assert_eq!(buf.len(), 1 + 2 + 16);
+    assert_eq!(&buf[..3], &[0x91, 0xc4, 0x10]);
+    assert_eq!(&buf[3..], &peer.addr.octets());
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!(peer, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn pass_smallvec_round_trips_as_array_like_vec() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+    use smallvec::{smallvec, SmallVec};
+
+    // Stays inline (capacity 4, 2 elements).
+    let inline: SmallVec<[u32; 4]> = smallvec![1, 2];
+    let mut buf = Vec::new();
+    inline.serialize(&mut Serializer::new(&mut buf)).unwrap();
+    assert_eq!(buf, rmps::to_vec(&vec![1u32, 2]).unwrap());
+
+    let mut de = Deserializer::new(&buf[..]);
+    let decoded: SmallVec<[u32; 4]> = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(inline, decoded);
+
+    // Spills onto the heap (capacity 4, 5 elements).
+    let spilled: SmallVec<[u32; 4]> = smallvec![1, 2, 3, 4, 5];
+    assert!(spilled.spilled());
+    let mut buf = Vec::new();
+    spilled.serialize(&mut Serializer::new(&mut buf)).unwrap();
+    assert_eq!(buf, rmps::to_vec(&vec![1u32, 2, 3, 4, 5]).unwrap());
+
+    let mut de = Deserializer::new(&buf[..]);
+    let decoded: SmallVec<[u32; 4]> = Deserialize::deserialize(&mut de).unwrap();
+    assert_eq!(spilled, decoded);
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn pass_compact_smallvec_bytes_round_trips_as_bin() {
+    use crate::rmps::{decode, Deserializer};
+    use serde::Deserialize;
+    use smallvec::SmallVec;
+
+    #[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Frame {
+        #[serde(serialize_with = "encode::compact_smallvec_bytes", deserialize_with = "decode::compact_smallvec_bytes")]
+        payload: SmallVec<[u8; 4]>,
+    }
+
+    // Inline payload.
+    let frame = Frame { payload: SmallVec::from_slice(&[1, 2, 3]) };
+    let mut buf = Vec::new();
+    frame.serialize(&mut Serializer::new(&mut buf)).unwrap();
+
+    // fixarray(1) wrapping the struct's one field, then bin8(3) with the 3 raw bytes.
+    assert_eq!(buf.len(), 1 + 2 + 3);
+    assert_eq!(&buf[..3], &[0x91, 0xc4, 0x03]);
+    assert_eq!(&buf[3..], frame.payload.as_slice());
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!(frame, Deserialize::deserialize(&mut de).unwrap());
+
+    // Spilled payload.
+    let frame = Frame { payload: SmallVec::from_slice(&[1, 2, 3, 4, 5, 6]) };
+    assert!(frame.payload.spilled());
+    let mut buf = Vec::new();
+    frame.serialize(&mut Serializer::new(&mut buf)).unwrap();
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!(frame, Deserialize::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_u128_max_round_trips_as_bin_in_binary_mode() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let value = u128::MAX;
+
+    let mut buf = Vec::new();
+    value.serialize(&mut Serializer::new(&mut buf)).unwrap();
+
+    // bin8(16) header, no decimal string in sight.
+    assert_eq!(&buf[..2], &[0xc4, 0x10]);
+
+    let mut de = Deserializer::new(&buf[..]);
+    assert_eq!(value, u128::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_u128_max_round_trips_as_string_in_human_readable_mode() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let value = u128::MAX;
+
+    let mut buf = Vec::new();
+    value.serialize(&mut Serializer::new(&mut buf).with_human_readable()).unwrap();
+
+    let mut de = Deserializer::new(&buf[..]).with_human_readable();
+    assert_eq!(value.to_string(), String::deserialize(&mut de).unwrap());
+
+    let mut de = Deserializer::new(&buf[..]).with_human_readable();
+    assert_eq!(value, u128::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_i128_min_round_trips_as_string_in_human_readable_mode() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let value = i128::MIN;
+
+    let mut buf = Vec::new();
+    value.serialize(&mut Serializer::new(&mut buf).with_human_readable()).unwrap();
+
+    let mut de = Deserializer::new(&buf[..]).with_human_readable();
+    assert_eq!(value.to_string(), String::deserialize(&mut de).unwrap());
+
+    let mut de = Deserializer::new(&buf[..]).with_human_readable();
+    assert_eq!(value, i128::deserialize(&mut de).unwrap());
+}
+
+#[test]
+fn pass_counting_write_reports_same_size_as_to_vec() {
+    #[derive(serde_derive::Serialize)]
+    struct Struct {
+        a: u32,
+        b: String,
+        c: Vec<i32>,
+    }
+
+    let value = Struct { a: 42, b: "hello".into(), c: vec![1, 2, 3] };
+
+    let mut counter = encode::CountingWrite::new();
+    value.serialize(&mut Serializer::new(&mut counter)).unwrap();
+
+    let buf = crate::rmps::to_vec(&value).unwrap();
+    assert_eq!(buf.len() as u64, counter.count());
+}
+
+#[test]
+fn pass_array_of_15_elements_round_trips_as_fixarray() {
+    let val: [i32; 15] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14];
+
+    let buf = crate::rmps::to_vec(&val).unwrap();
+    assert_eq!(0x9f, buf[0]); // fixarray with 15 elements
+
+    let actual: [i32; 15] = crate::rmps::from_slice(&buf).unwrap();
+    assert_eq!(val, actual);
+}
+
+#[test]
+fn pass_array_of_16_elements_round_trips_as_array16() {
+    let val: [i32; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+
+    let buf = crate::rmps::to_vec(&val).unwrap();
+    assert_eq!(0xdc, buf[0]); // array16, since 16 elements no longer fit in a fixarray
+    assert_eq!([0x00, 0x10], buf[1..3]);
+
+    let actual: [i32; 16] = crate::rmps::from_slice(&buf).unwrap();
+    assert_eq!(val, actual);
+}
+
+#[test]
+fn pass_vec_of_65535_elements_round_trips_as_array16() {
+    let val = vec![0u8; 65535];
+
+    let buf = crate::rmps::to_vec(&val).unwrap();
+    assert_eq!(0xdc, buf[0]); // array16, the largest length it still fits in
+    assert_eq!([0xff, 0xff], buf[1..3]);
+
+    let actual: Vec<u8> = crate::rmps::from_slice(&buf).unwrap();
+    assert_eq!(val, actual);
+}
+
+#[test]
+fn pass_vec_of_65536_elements_round_trips_as_array32() {
+    let val = vec![0u8; 65536];
+
+    let buf = crate::rmps::to_vec(&val).unwrap();
+    assert_eq!(0xdd, buf[0]); // array32, since 65536 elements overflow array16's u16 length
+    assert_eq!([0x00, 0x01, 0x00, 0x00], buf[1..5]);
+
+    let actual: Vec<u8> = crate::rmps::from_slice(&buf).unwrap();
+    assert_eq!(val, actual);
+}
+
+#[test]
+#[cfg(feature = "precompute-size")]
+fn pass_to_vec_allocates_exact_capacity_under_precompute_size() {
+    let val: Vec<u32> = (0..10_000).collect();
+
+    let expected_size = encode::serialized_size(&val).unwrap();
+    let buf = crate::rmps::to_vec(&val).unwrap();
+
+    assert_eq!(buf.capacity() as u64, expected_size);
+    assert_eq!(buf.len() as u64, expected_size);
+
+    let actual: Vec<u32> = crate::rmps::from_slice(&buf).unwrap();
+    assert_eq!(val, actual);
+}
+
+// `Branch` carries a second field purely so its on-wire shape (a 2-element array) differs from
+// `Leaf`'s (a plain integer), which forces it to serialize as a tuple variant (recursing through
+// `serialize_tuple_variant`/`Compound`) rather than as a newtype variant, which recurses straight
+// into the inner value without passing through a depth-counted compound at all.
+#[derive(serde_derive::Serialize)]
+enum Nested {
+    Leaf(u32),
+    Branch(Box<Nested>, u32),
+}
+
+#[test]
+fn fail_recursively_nested_struct_exceeding_max_depth() {
+    let mut value = Nested::Leaf(42);
+    for i in 0..10 {
+        value = Nested::Branch(Box::new(value), i);
+    }
+
+    let mut buf = Vec::new();
+    let err = value.serialize(&mut Serializer::new(&mut buf).with_max_depth(5)).unwrap_err();
+    assert!(matches!(err, Error::DepthLimitExceeded));
+}
+
+#[test]
+fn pass_recursively_nested_struct_within_max_depth() {
+    let mut value = Nested::Leaf(42);
+    for i in 0..10 {
+        value = Nested::Branch(Box::new(value), i);
+    }
+
+    let mut buf = Vec::new();
+    value.serialize(&mut Serializer::new(&mut buf).with_max_depth(64)).unwrap();
+}
+
+#[test]
+fn fail_with_max_depth_0_errors_on_first_compound_instead_of_underflowing() {
+    let value = Nested::Branch(Box::new(Nested::Leaf(42)), 0);
+
+    let mut buf = Vec::new();
+    let err = value.serialize(&mut Serializer::new(&mut buf).with_max_depth(0)).unwrap_err();
+    assert!(matches!(err, Error::DepthLimitExceeded));
+}
+
+#[test]
+fn pass_with_max_depth_1_allows_exactly_one_compound() {
+    // One `Branch` is one tuple-variant compound; `Leaf` recurses straight into its inner value
+    // without entering a depth-counted compound at all (see the comment on `Nested`), so this is
+    // exactly the one level of nesting `with_max_depth(1)` is documented to allow.
+    let value = Nested::Branch(Box::new(Nested::Leaf(42)), 0);
+
+    let mut buf = Vec::new();
+    value.serialize(&mut Serializer::new(&mut buf).with_max_depth(1)).unwrap();
+}
+
+#[test]
+fn fail_with_max_depth_1_errors_on_second_compound() {
+    let value = Nested::Branch(Box::new(Nested::Branch(Box::new(Nested::Leaf(42)), 1)), 0);
+
+    let mut buf = Vec::new();
+    let err = value.serialize(&mut Serializer::new(&mut buf).with_max_depth(1)).unwrap_err();
+    assert!(matches!(err, Error::DepthLimitExceeded));
+}
+
+#[test]
+fn pass_with_ext_durations_round_trips() {
+    use std::time::Duration;
+
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let durations = [
+        Duration::new(0, 0),
+        Duration::new(253_402_300_799, 0),
+        Duration::new(1, 500_000_000),
+    ];
+
+    for duration in durations {
+        let mut buf = Vec::new();
+        duration.serialize(&mut Serializer::new(&mut buf).with_ext_durations()).unwrap();
+
+        // ext8(0xc7) since the 12-byte payload doesn't fit a fixext preset size, a 1-byte length
+        // of 12, the DURATION_EXT_TYPE tag, then 8 bytes of secs and 4 of nanos.
+        assert_eq!(buf.len(), 1 + 1 + 1 + 8 + 4);
+        assert_eq!(&buf[..3], &[0xc7, 0x0c, 0x01]);
+
+        let mut de = Deserializer::new(&buf[..]).with_ext_durations();
+        assert_eq!(duration, Duration::deserialize(&mut de).unwrap());
+    }
+}
+
+#[test]
+fn pass_to_vec_reuse_round_trips_1000_messages_through_one_buffer() {
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let mut buf = Vec::new();
+    for i in 0..1000u32 {
+        encode::to_vec_reuse(&mut buf, &(i, i.to_string())).unwrap();
+
+        let mut de = Deserializer::new(&buf[..]);
+        let actual: (u32, String) = Deserialize::deserialize(&mut de).unwrap();
+        assert_eq!((i, i.to_string()), actual);
+    }
+}
+
+#[test]
+fn pass_with_ext_durations_still_reads_back_plain_struct_encoding() {
+    use std::time::Duration;
+
+    use crate::rmps::Deserializer;
+    use serde::Deserialize;
+
+    let duration = Duration::new(12, 345);
+
+    // Written the ordinary way, without `with_ext_durations` on the writer.
+    let mut buf = Vec::new();
+    duration.serialize(&mut Serializer::new(&mut buf)).unwrap();
+
+    // A reader with `with_ext_durations` enabled still falls through to the struct form, since
+    // the wire value isn't ext-shaped.
+    let mut de = Deserializer::new(&buf[..]).with_ext_durations();
+    assert_eq!(duration, Duration::deserialize(&mut de).unwrap());
+}
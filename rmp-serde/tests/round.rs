@@ -252,6 +252,47 @@ fn round_trip_untagged_enum_with_enum_associated_data() {
     assert_eq!(data4_1, data4_2);
 }
 
+// `Branch` carries a second field purely so its on-wire shape (a 2-element array) differs from
+// `Leaf`'s (a plain integer, since a 1-tuple variant serializes transparently); otherwise every
+// `Branch` would be indistinguishable from, and decode as, a `Leaf`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum NestedUntagged {
+    Leaf(u32),
+    Branch(Box<NestedUntagged>, u32),
+}
+
+#[test]
+fn round_trip_moderately_nested_untagged_enum() {
+    let mut value = NestedUntagged::Leaf(42);
+    for i in 0..50 {
+        value = NestedUntagged::Branch(Box::new(value), i);
+    }
+
+    let bytes = rmps::to_vec(&value).unwrap();
+    let decoded: NestedUntagged = rmps::from_slice(&bytes).unwrap();
+    assert_eq!(value, decoded);
+}
+
+#[test]
+fn fail_untagged_enum_buffering_respects_depth_limit() {
+    // `#[serde(untagged)]` buffers the whole payload into a `Content` tree before picking a
+    // variant, recursing through `Deserializer::deserialize_any` once per nesting level. That
+    // recursion is bounded by the same depth limit as ordinary nested seqs/maps (see
+    // `Deserializer::set_max_depth`), so a pathological depth errs cleanly instead of exhausting
+    // the stack or allocating unbounded `Content` nodes.
+    let mut value = NestedUntagged::Leaf(42);
+    for i in 0..10 {
+        value = NestedUntagged::Branch(Box::new(value), i);
+    }
+    let bytes = rmps::to_vec(&value).unwrap();
+
+    let mut de = Deserializer::new(&bytes[..]);
+    de.set_max_depth(5);
+    let res: Result<NestedUntagged, _> = Deserialize::deserialize(&mut de);
+    assert!(matches!(res, Err(rmps::decode::Error::DepthLimitExceeded)));
+}
+
 // Checks whether deserialization and serialization can both work with structs as maps
 #[test]
 fn round_struct_as_map() {